@@ -0,0 +1,402 @@
+//! Analysis module for project-wide checks on the resolved dependency graph
+//! Works on parse results plus the node ids every import resolved to
+
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use petgraph::algo::{greedy_feedback_arc_set, is_cyclic_directed, tarjan_scc};
+use petgraph::graph::{DiGraph, EdgeIndex, NodeIndex};
+use petgraph::visit::{EdgeFiltered, EdgeRef};
+
+use crate::parser_module::{ImportKind, ParseResult};
+
+/// Node ids each import resolved to, per file, in the order of `ParseResult::imports`
+pub type ResolvedImports = HashMap<String, Vec<Vec<String>>>;
+
+/// Exports of every file that no other file uses, keyed by file id
+///
+/// Named imports mark single exports as used, namespace imports (`* as x`,
+/// `import()`, `require`) mark all of them. Uses of a re-exported name are
+/// followed through barrel files to the module that defines it, so a
+/// re-export only keeps its origin alive when something imports it.
+pub fn find_unused_exports(
+    parse_results: &HashMap<String, ParseResult>,
+    resolved: &ResolvedImports,
+) -> BTreeMap<String, Vec<String>> {
+    let mut usage = ExportUsage {
+        parse_results,
+        resolved,
+        used: HashSet::new(),
+        fully_used: HashSet::new(),
+    };
+
+    for (file, result) in parse_results {
+        let Some(targets) = resolved.get(file) else {
+            continue;
+        };
+        for (import, targets) in result.imports.iter().zip(targets) {
+            // Re-exports only count once something imports them
+            if !matches!(import.kind, ImportKind::Static | ImportKind::Dynamic) {
+                continue;
+            }
+            for target in targets.iter().filter(|t| *t != file) {
+                if import.specifiers.is_empty() {
+                    // `require`/`import()` expose the whole module; bare `import './x'` uses nothing
+                    if import.is_namespace || import.is_default {
+                        usage.mark_all(target, true);
+                    }
+                }
+                for specifier in &import.specifiers {
+                    if specifier.imported == "*" {
+                        usage.mark_all(target, true);
+                    } else {
+                        usage.mark_used(target, &specifier.imported);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut unused = BTreeMap::new();
+    for (file, result) in parse_results {
+        if result.exports.is_empty() {
+            continue;
+        }
+        let mut names: Vec<String> = result
+            .exports
+            .iter()
+            .filter(|e| e.name != "*" && !usage.used.contains(&(file.clone(), e.name.clone())))
+            .map(|e| e.name.clone())
+            .collect();
+        names.sort();
+        names.dedup();
+        unused.insert(file.clone(), names);
+    }
+    unused
+}
+
+/// Node ids reachable from `entries` by following links from source to target
+pub fn find_reachable<'a>(
+    entries: impl IntoIterator<Item = &'a str>,
+    links: impl IntoIterator<Item = (&'a str, &'a str)>,
+) -> HashSet<String> {
+    let mut adjacency: HashMap<&str, Vec<&str>> = HashMap::new();
+    for (source, target) in links {
+        adjacency.entry(source).or_default().push(target);
+    }
+
+    let mut reached: HashSet<String> = HashSet::new();
+    let mut queue: VecDeque<&str> = VecDeque::new();
+    for entry in entries {
+        if reached.insert(entry.to_string()) {
+            queue.push_back(entry);
+        }
+    }
+
+    while let Some(node) = queue.pop_front() {
+        for &next in adjacency.get(node).into_iter().flatten() {
+            if reached.insert(next.to_string()) {
+                queue.push_back(next);
+            }
+        }
+    }
+    reached
+}
+
+/// A group of nodes that (transitively) depend on each other
+#[derive(Debug)]
+pub struct Cycle {
+    /// Node ids of the strongly connected component, sorted
+    pub nodes: Vec<String>,
+    /// Links (source, target) whose removal leaves the component acyclic
+    pub breaking_edges: Vec<(String, String)>,
+}
+
+/// Strongly connected components (Tarjan) of more than one node, each with a
+/// minimal set of edges that breaks all of its cycles
+///
+/// Finding the smallest such set is NP-hard; the greedy feedback arc set is
+/// pruned until no edge can be kept without reintroducing a cycle.
+pub fn find_cycles<'a>(links: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<Cycle> {
+    let mut graph: DiGraph<&str, ()> = DiGraph::new();
+    let mut indices: HashMap<&str, NodeIndex> = HashMap::new();
+    let mut seen = HashSet::new();
+    for (source, target) in links {
+        let source = *indices.entry(source).or_insert_with(|| graph.add_node(source));
+        let target = *indices.entry(target).or_insert_with(|| graph.add_node(target));
+        if source != target && seen.insert((source, target)) {
+            graph.add_edge(source, target, ());
+        }
+    }
+
+    let mut cycles: Vec<Cycle> = tarjan_scc(&graph)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| {
+            // Work on the component alone so edges leaving it are never candidates
+            let members: HashMap<NodeIndex, NodeIndex> =
+                component.iter().enumerate().map(|(i, n)| (*n, NodeIndex::new(i))).collect();
+            let mut subgraph: DiGraph<&str, ()> = DiGraph::new();
+            for node in &component {
+                subgraph.add_node(graph[*node]);
+            }
+            for edge in graph.edge_references() {
+                if let (Some(&s), Some(&t)) = (members.get(&edge.source()), members.get(&edge.target())) {
+                    subgraph.add_edge(s, t, ());
+                }
+            }
+
+            let mut breaking_edges: Vec<(String, String)> = minimal_feedback_arc_set(&subgraph)
+                .into_iter()
+                .filter_map(|e| subgraph.edge_endpoints(e))
+                .map(|(s, t)| (subgraph[s].to_string(), subgraph[t].to_string()))
+                .collect();
+            breaking_edges.sort();
+
+            let mut nodes: Vec<String> = component.iter().map(|n| graph[*n].to_string()).collect();
+            nodes.sort();
+            Cycle { nodes, breaking_edges }
+        })
+        .collect();
+
+    cycles.sort_by(|a, b| a.nodes.cmp(&b.nodes));
+    cycles
+}
+
+fn minimal_feedback_arc_set(graph: &DiGraph<&str, ()>) -> Vec<EdgeIndex> {
+    let mut removed: Vec<EdgeIndex> = greedy_feedback_arc_set(graph).map(|e| e.id()).collect();
+
+    // Put edges back one at a time while the graph stays acyclic without them
+    let mut i = 0;
+    while i < removed.len() {
+        let others: HashSet<EdgeIndex> = removed.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, e)| *e).collect();
+        let remaining = EdgeFiltered::from_fn(graph, |e| !others.contains(&e.id()));
+        if is_cyclic_directed(&remaining) {
+            i += 1;
+        } else {
+            removed.remove(i);
+        }
+    }
+    removed
+}
+
+/// Export usage propagated through re-export chains
+struct ExportUsage<'a> {
+    parse_results: &'a HashMap<String, ParseResult>,
+    resolved: &'a ResolvedImports,
+    /// (file, export name) pairs that are used
+    used: HashSet<(String, String)>,
+    /// Files whose exports were all marked used (with or without the default export)
+    fully_used: HashSet<(String, bool)>,
+}
+
+impl ExportUsage<'_> {
+    fn mark_used(&mut self, file: &str, name: &str) {
+        if !self.used.insert((file.to_string(), name.to_string())) {
+            return;
+        }
+        let Some(result) = self.parse_results.get(file) else {
+            return;
+        };
+
+        let mut defined = false;
+        for export in result.exports.iter().filter(|e| e.name == name) {
+            defined = true;
+            if let (Some(source), Some(imported)) = (&export.source, &export.imported) {
+                for target in self.reexport_targets(file, source) {
+                    if imported == "*" {
+                        // export * as ns from './mod'
+                        self.mark_all(&target, true);
+                    } else {
+                        self.mark_used(&target, imported);
+                    }
+                }
+            }
+        }
+
+        // `export * from` forwards every named export except `default`
+        if !defined && name != "default" {
+            for export in result.exports.iter().filter(|e| e.name == "*") {
+                if let Some(source) = &export.source {
+                    for target in self.reexport_targets(file, source) {
+                        self.mark_used(&target, name);
+                    }
+                }
+            }
+        }
+    }
+
+    fn mark_all(&mut self, file: &str, include_default: bool) {
+        if !self.fully_used.insert((file.to_string(), include_default)) {
+            return;
+        }
+        let Some(result) = self.parse_results.get(file) else {
+            return;
+        };
+
+        for export in &result.exports {
+            if export.name == "*" {
+                if let Some(source) = &export.source {
+                    for target in self.reexport_targets(file, source) {
+                        self.mark_all(&target, false);
+                    }
+                }
+            } else if include_default || !export.is_default {
+                self.mark_used(file, &export.name);
+            }
+        }
+    }
+
+    /// Files a re-export of `source` in `file` resolved to
+    fn reexport_targets(&self, file: &str, source: &str) -> Vec<String> {
+        let (Some(result), Some(targets)) = (self.parse_results.get(file), self.resolved.get(file)) else {
+            return Vec::new();
+        };
+
+        result
+            .imports
+            .iter()
+            .zip(targets)
+            .filter(|(import, _)| import.kind == ImportKind::ReExport && import.source == source)
+            .flat_map(|(_, targets)| targets.iter().filter(|t| *t != file).cloned())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::walker_module::glob_matcher;
+    use crate::parser_module::{ExportInfo, ImportInfo, ImportSpecifier};
+
+    fn export(name: &str) -> ExportInfo {
+        ExportInfo {
+            name: name.to_string(),
+            is_default: name == "default",
+            source: None,
+            imported: None,
+        }
+    }
+
+    fn reexport(name: &str, source: &str, imported: &str) -> ExportInfo {
+        ExportInfo {
+            source: Some(source.to_string()),
+            imported: Some(imported.to_string()),
+            ..export(name)
+        }
+    }
+
+    fn import(source: &str, kind: ImportKind, names: &[&str]) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            is_default: false,
+            is_namespace: names.contains(&"*"),
+            line: 1,
+            kind,
+            specifiers: names
+                .iter()
+                .map(|n| ImportSpecifier {
+                    imported: n.to_string(),
+                    local: n.to_string(),
+                    is_type: false,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_unused_exports_through_reexports() {
+        let mut parse_results = HashMap::new();
+        let mut resolved = ResolvedImports::new();
+        let mut add = |file: &str, imports: Vec<(ImportInfo, &str)>, exports: Vec<ExportInfo>| {
+            resolved.insert(file.to_string(), imports.iter().map(|(_, t)| vec![t.to_string()]).collect());
+            parse_results.insert(
+                file.to_string(),
+                ParseResult {
+                    imports: imports.into_iter().map(|(i, _)| i).collect(),
+                    exports,
+                    ..Default::default()
+                },
+            );
+        };
+
+        add(
+            "app.ts",
+            vec![
+                (import("./ui", ImportKind::Static, &["Button", "icons"]), "ui/index.ts"),
+                (import("./api", ImportKind::Static, &["*"]), "api.ts"),
+            ],
+            vec![export("default")],
+        );
+        add(
+            "ui/index.ts",
+            vec![
+                (import("./button", ImportKind::ReExport, &["Button", "LegacyButton"]), "ui/button.ts"),
+                (import("./card", ImportKind::ReExport, &["*"]), "ui/card.ts"),
+                (import("./icons", ImportKind::ReExport, &["*"]), "ui/icons.ts"),
+            ],
+            vec![
+                reexport("Button", "./button", "Button"),
+                reexport("LegacyButton", "./button", "LegacyButton"),
+                reexport("*", "./card", "*"),
+                reexport("icons", "./icons", "*"),
+            ],
+        );
+        add("ui/button.ts", vec![], vec![export("Button"), export("LegacyButton"), export("ButtonProps")]);
+        add("ui/card.ts", vec![], vec![export("Card")]);
+        add("ui/icons.ts", vec![], vec![export("Plus"), export("default")]);
+        add("api.ts", vec![], vec![export("fetchUser"), export("default")]);
+
+        let unused = find_unused_exports(&parse_results, &resolved);
+
+        assert_eq!(unused["app.ts"], vec!["default"]);
+        assert_eq!(unused["ui/index.ts"], vec!["LegacyButton"]);
+        assert_eq!(unused["ui/button.ts"], vec!["ButtonProps", "LegacyButton"]);
+        assert_eq!(unused["ui/card.ts"], vec!["Card"]);
+        assert!(unused["ui/icons.ts"].is_empty());
+        assert!(unused["api.ts"].is_empty());
+    }
+
+    #[test]
+    fn test_reachability_from_entry_globs() {
+        let files = ["src/main.ts", "src/app.ts", "src/routes/home.ts", "src/util.ts", "src/old.ts", "src/app.test.ts"];
+        let links = [
+            ("src/main.ts", "src/app.ts"),
+            ("src/routes/home.ts", "src/util.ts"),
+            ("src/old.ts", "src/util.ts"),
+        ];
+
+        let matcher = glob_matcher(&["./src/main.ts".to_string(), "src/routes/**".to_string(), "**/*.test.ts".to_string()]).unwrap();
+        let entries = files.iter().copied().filter(|f| matcher.is_match(f));
+        let reached = find_reachable(entries, links.iter().copied());
+
+        let mut unreachable: Vec<_> = files.iter().filter(|f| !reached.contains(**f)).collect();
+        unreachable.sort();
+        assert_eq!(unreachable, vec![&"src/old.ts"]);
+    }
+
+    #[test]
+    fn test_find_cycles_with_breaking_edges() {
+        let links = [
+            ("a", "b"),
+            ("b", "c"),
+            ("c", "a"),
+            ("c", "b"),
+            ("c", "d"),
+            ("x", "y"),
+            ("y", "x"),
+        ];
+
+        let cycles = find_cycles(links.iter().copied());
+
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].nodes, vec!["a", "b", "c"]);
+        assert_eq!(cycles[1].nodes, vec!["x", "y"]);
+
+        // Every cycle through a, b, c uses b -> c, so one edge suffices
+        assert_eq!(cycles[0].breaking_edges.len(), 1);
+        let (s, t) = &cycles[0].breaking_edges[0];
+        let remaining: Vec<_> = links[..5].iter().filter(|(ls, lt)| (ls, lt) != (&s.as_str(), &t.as_str())).copied().collect();
+        assert!(find_cycles(remaining).is_empty());
+        assert_eq!(cycles[1].breaking_edges.len(), 1);
+    }
+}
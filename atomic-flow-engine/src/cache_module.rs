@@ -0,0 +1,187 @@
+//! Cache module for reusing parse results between runs
+//! Stores one `ParseResult` per file under `.atomicflow/cache`, keyed by path, size and mtime
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::parser_module::ParseResult;
+
+/// Bump whenever `ParseResult` or any parser's output changes, so stale entries are dropped
+const CACHE_VERSION: u32 = 2;
+
+/// Cache location relative to the project root
+const CACHE_DIR: &str = ".atomicflow/cache";
+const CACHE_FILE: &str = "parse-cache.json";
+
+/// Size and modification time a cached result was computed for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub size: u64,
+    /// Nanoseconds since the Unix epoch
+    pub mtime: u64,
+}
+
+impl FileStamp {
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            mtime: mtime.as_nanos() as u64,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    stamp: FileStamp,
+    result: ParseResult,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheFile {
+    version: u32,
+    /// Engine version that wrote the cache; upgrades start from scratch
+    engine: String,
+    entries: HashMap<String, CacheEntry>,
+}
+
+/// Parse results of the previous run plus those of the current one
+pub struct ParseCache {
+    /// Cache file on disk, `None` for a cache that only lives in memory
+    file: Option<PathBuf>,
+    previous: HashMap<String, CacheEntry>,
+    current: HashMap<String, CacheEntry>,
+    /// Whether `current` holds results `previous` does not
+    changed: bool,
+}
+
+impl ParseCache {
+    /// Load the cache of the project at `root`; a missing, unreadable or
+    /// outdated cache file yields an empty cache
+    pub fn load(root: &Path) -> Self {
+        let file = root.join(CACHE_DIR).join(CACHE_FILE);
+        let previous = fs::read(&file)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<CacheFile>(&bytes).ok())
+            .filter(|cache| cache.version == CACHE_VERSION && cache.engine == env!("CARGO_PKG_VERSION"))
+            .map(|cache| cache.entries)
+            .unwrap_or_default();
+
+        Self {
+            file: Some(file),
+            previous,
+            current: HashMap::new(),
+            changed: false,
+        }
+    }
+
+    /// Empty cache that is never written to disk (watch mode with `--no-cache`)
+    pub fn in_memory() -> Self {
+        Self {
+            file: None,
+            previous: HashMap::new(),
+            current: HashMap::new(),
+            changed: false,
+        }
+    }
+
+    /// Cached result for `id` if the file is unchanged since it was parsed
+    pub fn get(&self, id: &str, stamp: FileStamp) -> Option<ParseResult> {
+        self.previous
+            .get(id)
+            .filter(|entry| entry.stamp == stamp)
+            .map(|entry| entry.result.clone())
+    }
+
+    /// Drop the cached result for `id` so the next run parses it again
+    pub fn forget(&mut self, id: &str) {
+        self.previous.remove(id);
+    }
+
+    /// Record the result for `id`, whether it came from the cache or a fresh parse
+    pub fn insert(&mut self, id: &str, stamp: FileStamp, result: &ParseResult) {
+        if self.previous.get(id).is_none_or(|entry| entry.stamp != stamp) {
+            self.changed = true;
+        }
+        self.current.insert(id.to_string(), CacheEntry { stamp, result: result.clone() });
+    }
+
+    /// End a run: the results recorded in it become the cache for the next one
+    /// (dropping files that no longer exist) and are written to disk if anything changed
+    pub fn finish_run(&mut self) -> Result<()> {
+        let changed = self.changed || self.current.len() != self.previous.len();
+        self.previous = std::mem::take(&mut self.current);
+        self.changed = false;
+
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+        if !changed {
+            return Ok(());
+        }
+
+        let cache = CacheFile {
+            version: CACHE_VERSION,
+            engine: env!("CARGO_PKG_VERSION").to_string(),
+            entries: std::mem::take(&mut self.previous),
+        };
+        let bytes = serde_json::to_vec(&cache);
+        self.previous = cache.entries;
+
+        if let Some(dir) = file.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        // Write then rename so an interrupted run never leaves a truncated cache
+        let temp = file.with_extension("json.tmp");
+        fs::write(&temp, bytes?)?;
+        fs::rename(&temp, file)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip_and_invalidation() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let stamp = FileStamp { size: 10, mtime: 42 };
+        let result = ParseResult {
+            line_count: 3,
+            packages: vec!["app".to_string()],
+            ..Default::default()
+        };
+
+        let mut cache = ParseCache::load(root);
+        assert!(cache.get("src/a.ts", stamp).is_none());
+        cache.insert("src/a.ts", stamp, &result);
+        cache.finish_run().unwrap();
+        // The finished run is also kept in memory for the next one
+        assert!(cache.get("src/a.ts", stamp).is_some());
+
+        let cache = ParseCache::load(root);
+        let cached = cache.get("src/a.ts", stamp).unwrap();
+        assert_eq!((cached.line_count, cached.packages), (3, vec!["app".to_string()]));
+        // Any change to size or mtime is a miss
+        assert!(cache.get("src/a.ts", FileStamp { size: 11, mtime: 42 }).is_none());
+
+        // Caches written with another format version are discarded
+        let file = root.join(CACHE_DIR).join(CACHE_FILE);
+        let outdated = fs::read_to_string(&file).unwrap().replacen(
+            &format!("\"version\":{}", CACHE_VERSION),
+            &format!("\"version\":{}", CACHE_VERSION + 1),
+            1,
+        );
+        fs::write(&file, outdated).unwrap();
+        assert!(ParseCache::load(root).get("src/a.ts", stamp).is_none());
+    }
+}
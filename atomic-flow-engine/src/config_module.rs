@@ -0,0 +1,534 @@
+//! Config module for project configuration that changes how imports resolve
+//! Reads `tsconfig.json`/`jsconfig.json` (following `extends`) for `baseUrl` and `paths`,
+//! Vite/webpack `resolve.alias`, the manual aliases of `atomicflow.json` and workspace packages
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::package_module::{find_workspace_packages, split_package_specifier, WorkspacePackage};
+use crate::parser_module::{parse_bundler_aliases, Replacement};
+use crate::resolver_module::{join_path, split_parent};
+
+/// Config files that define import aliases, in order of precedence within a directory
+const TS_CONFIG_FILES: [&str; 2] = ["tsconfig.json", "jsconfig.json"];
+
+/// Bundler configs whose `resolve.alias` entries apply to the files below them
+const BUNDLER_CONFIG_FILES: [&str; 11] = [
+    "vite.config.ts",
+    "vite.config.mts",
+    "vite.config.js",
+    "vite.config.mjs",
+    "vite.config.cjs",
+    "webpack.config.js",
+    "webpack.config.ts",
+    "webpack.config.cjs",
+    "webpack.config.mjs",
+    "vue.config.js",
+    "vue.config.ts",
+];
+
+/// Engine config file at the project root
+pub const ENGINE_CONFIG_FILE: &str = "atomicflow.json";
+
+/// Longest `extends` chain followed before giving up (guards against loops)
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// Resolution settings found in the project
+#[derive(Debug, Clone, Default)]
+pub struct ProjectConfig {
+    /// One entry per directory with a tsconfig/jsconfig, deepest directory first
+    pub ts_configs: Vec<TsConfig>,
+    /// Aliases of each directory with bundler configs, deepest directory first
+    pub bundler_aliases: Vec<(String, Vec<Alias>)>,
+    /// Aliases from `atomicflow.json`, tried after the bundler's, longest first
+    pub manual_aliases: Vec<Alias>,
+    /// npm/yarn/pnpm workspace packages, sorted by name
+    pub workspace_packages: Vec<WorkspacePackage>,
+}
+
+/// A specifier prefix mapped to a path, or to another package
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alias {
+    pub find: String,
+    /// Only the exact specifier matches, not `find/...`
+    pub exact: bool,
+    /// Replacement, relative to the project root (a package specifier if `package`)
+    pub target: String,
+    /// The target is another package (`vue$: 'vue/dist/vue.esm.js'`), not a project path
+    pub package: bool,
+}
+
+impl Alias {
+    /// Path `specifier` maps to, if this alias matches it
+    pub fn apply(&self, specifier: &str) -> Option<String> {
+        if self.package {
+            return None;
+        }
+        match self.rest(specifier)? {
+            "" => Some(self.target.clone()),
+            rest => join_path(&self.target, rest),
+        }
+    }
+
+    /// Package specifier `specifier` is redirected to, if this alias matches it
+    pub fn redirect(&self, specifier: &str) -> Option<String> {
+        if !self.package {
+            return None;
+        }
+        match self.rest(specifier)? {
+            "" => Some(self.target.clone()),
+            rest => Some(format!("{}/{}", self.target.trim_end_matches('/'), rest)),
+        }
+    }
+
+    /// What follows `find` in a matching specifier (`""` for the specifier itself)
+    fn rest<'s>(&self, specifier: &'s str) -> Option<&'s str> {
+        if specifier == self.find {
+            return Some("");
+        }
+        if self.exact {
+            return None;
+        }
+        let rest = specifier.strip_prefix(&self.find)?;
+        let rest = if self.find.ends_with('/') { rest } else { rest.strip_prefix('/')? };
+        (!rest.is_empty()).then_some(rest)
+    }
+}
+
+/// Contents of `atomicflow.json`
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct EngineConfig {
+    /// Specifier prefix => path relative to the project root (`key$` matches exactly)
+    aliases: BTreeMap<String, String>,
+}
+
+/// Effective `baseUrl` and `paths` of one tsconfig/jsconfig, for the files below its directory
+///
+/// All paths are relative to the project root.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TsConfig {
+    pub dir: String,
+    pub base_url: Option<String>,
+    /// Patterns (`@app/*`, `utils`) with their target paths, `*` kept in place
+    pub paths: Vec<(String, Vec<String>)>,
+}
+
+impl ProjectConfig {
+    /// Load every tsconfig/jsconfig among the scanned files; broken configs are
+    /// reported on stderr and skipped
+    pub fn load(root: &Path, node_ids: &HashSet<String>) -> Self {
+        let mut ts_configs = Vec::new();
+        let mut seen_dirs = HashSet::new();
+        for file_name in TS_CONFIG_FILES {
+            let mut ids: Vec<&String> = node_ids
+                .iter()
+                .filter(|id| split_parent(id).1 == file_name)
+                .collect();
+            ids.sort();
+            for id in ids {
+                let dir = split_parent(id).0;
+                if !seen_dirs.insert(dir.to_string()) {
+                    continue;
+                }
+                match load_ts_config(root, id) {
+                    Ok(config) => ts_configs.push(config),
+                    Err(err) => eprintln!("Warning: ignoring {}: {:#}", id, err),
+                }
+            }
+        }
+
+        ts_configs.sort_by(|a: &TsConfig, b: &TsConfig| b.dir.len().cmp(&a.dir.len()).then(a.dir.cmp(&b.dir)));
+
+        let mut bundler_aliases: Vec<(String, Vec<Alias>)> = Vec::new();
+        for file_name in BUNDLER_CONFIG_FILES {
+            let mut ids: Vec<&String> = node_ids.iter().filter(|id| split_parent(id).1 == file_name).collect();
+            ids.sort();
+            for id in ids {
+                let dir = split_parent(id).0;
+                let aliases = match parse_bundler_aliases(&root.join(id)) {
+                    Ok(aliases) => aliases,
+                    Err(err) => {
+                        eprintln!("Warning: ignoring {}: {:#}", id, err);
+                        continue;
+                    }
+                };
+                let aliases = aliases.into_iter().filter_map(|alias| {
+                    let (target, package) = match alias.replacement {
+                        Replacement::Path(path) => (join_path(dir, &path)?, false),
+                        Replacement::Package(specifier) => (specifier, true),
+                    };
+                    Some(Alias {
+                        find: alias.find,
+                        exact: alias.exact,
+                        target,
+                        package,
+                    })
+                });
+                match bundler_aliases.iter_mut().find(|(d, _)| d == dir) {
+                    Some((_, existing)) => existing.extend(aliases),
+                    None => bundler_aliases.push((dir.to_string(), aliases.collect())),
+                }
+            }
+        }
+        bundler_aliases.sort_by(|a, b| b.0.len().cmp(&a.0.len()).then(a.0.cmp(&b.0)));
+
+        let manual_aliases = match load_engine_config(root) {
+            Ok(config) => {
+                let mut aliases: Vec<Alias> = config
+                    .aliases
+                    .into_iter()
+                    .filter_map(|(key, target)| {
+                        let (find, exact) = match key.strip_suffix('$') {
+                            Some(find) => (find.to_string(), true),
+                            None => (key, false),
+                        };
+                        Some(Alias {
+                            find,
+                            exact,
+                            target: join_path("", &target)?,
+                            package: false,
+                        })
+                    })
+                    .collect();
+                aliases.sort_by_key(|a| std::cmp::Reverse(a.find.len()));
+                aliases
+            }
+            Err(err) => {
+                eprintln!("Warning: ignoring {}: {:#}", ENGINE_CONFIG_FILE, err);
+                Vec::new()
+            }
+        };
+
+        Self {
+            ts_configs,
+            bundler_aliases,
+            manual_aliases,
+            workspace_packages: find_workspace_packages(root, node_ids),
+        }
+    }
+
+    /// The tsconfig/jsconfig that applies to `file`: the one in its nearest ancestor directory
+    pub fn ts_config_for(&self, file: &str) -> Option<&TsConfig> {
+        self.ts_configs
+            .iter()
+            .find(|config| config.dir.is_empty() || file.starts_with(&format!("{}/", config.dir)))
+    }
+
+    /// Aliases that apply to `file`: its nearest bundler config's, then the manual ones
+    pub fn aliases_for<'s>(&'s self, file: &str) -> impl Iterator<Item = &'s Alias> {
+        let bundler = self
+            .bundler_aliases
+            .iter()
+            .find(|(dir, _)| dir.is_empty() || file.starts_with(&format!("{}/", dir)))
+            .map(|(_, aliases)| aliases.as_slice())
+            .unwrap_or_default();
+        bundler.iter().chain(&self.manual_aliases)
+    }
+
+    /// Files a bare specifier (`@acme/ui`, `@acme/ui/button`) may resolve to when it
+    /// names a workspace package, best first
+    pub fn workspace_candidates(&self, specifier: &str) -> Vec<String> {
+        let Some((name, subpath)) = split_package_specifier(specifier) else {
+            return Vec::new();
+        };
+        self.workspace_packages
+            .binary_search_by(|p| p.name.as_str().cmp(name))
+            .map(|i| self.workspace_packages[i].candidates(&subpath))
+            .unwrap_or_default()
+    }
+
+    /// Whether `name` is a package of one of the project's workspaces
+    pub fn is_workspace_package(&self, name: &str) -> bool {
+        self.workspace_packages
+            .binary_search_by(|p| p.name.as_str().cmp(name))
+            .is_ok()
+    }
+}
+
+/// `atomicflow.json` at the project root; a missing file is an empty config
+fn load_engine_config(root: &Path) -> Result<EngineConfig> {
+    let path = root.join(ENGINE_CONFIG_FILE);
+    if !path.exists() {
+        return Ok(EngineConfig::default());
+    }
+    let content = fs::read_to_string(&path)?;
+    Ok(serde_json::from_str(&strip_jsonc(&content))?)
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct RawTsConfig {
+    #[serde(default)]
+    extends: Extends,
+    #[serde(default)]
+    compiler_options: RawCompilerOptions,
+}
+
+/// `extends` is a single config or, since TypeScript 5.0, a list applied in order
+#[derive(Deserialize, Default)]
+#[serde(untagged)]
+enum Extends {
+    #[default]
+    None,
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct RawCompilerOptions {
+    base_url: Option<String>,
+    paths: Option<BTreeMap<String, Vec<String>>>,
+}
+
+/// Options collected along an `extends` chain; later configs override earlier ones
+#[derive(Default)]
+struct Inherited {
+    base_url: Option<String>,
+    /// Patterns with their raw targets and the directory of the config declaring them
+    paths: Option<(BTreeMap<String, Vec<String>>, String)>,
+}
+
+fn load_ts_config(root: &Path, id: &str) -> Result<TsConfig> {
+    let mut inherited = Inherited::default();
+    apply_config(root, id, &mut inherited, 0)?;
+
+    // `paths` are relative to `baseUrl` when set, otherwise to the config declaring them
+    let paths = match inherited.paths {
+        Some((patterns, declared_in)) => {
+            let base = inherited.base_url.clone().unwrap_or(declared_in);
+            patterns
+                .into_iter()
+                .map(|(pattern, targets)| {
+                    let targets = targets.iter().filter_map(|t| join_path(&base, t)).collect();
+                    (pattern, targets)
+                })
+                .collect()
+        }
+        None => Vec::new(),
+    };
+
+    Ok(TsConfig {
+        dir: split_parent(id).0.to_string(),
+        base_url: inherited.base_url,
+        paths,
+    })
+}
+
+/// Apply the config at `id` (and, first, everything it extends) onto `inherited`
+fn apply_config(root: &Path, id: &str, inherited: &mut Inherited, depth: usize) -> Result<()> {
+    if depth > MAX_EXTENDS_DEPTH {
+        bail!("`extends` chain is too long at {}", id);
+    }
+
+    let content = fs::read_to_string(root.join(id)).with_context(|| format!("Failed to read {}", id))?;
+    let raw: RawTsConfig = serde_json::from_str(&strip_jsonc(&content)).with_context(|| format!("Invalid JSON in {}", id))?;
+    let dir = split_parent(id).0;
+
+    let extends = match raw.extends {
+        Extends::None => Vec::new(),
+        Extends::One(base) => vec![base],
+        Extends::Many(bases) => bases,
+    };
+    for base in extends {
+        // Bases outside the project (a parent directory) cannot be read by id
+        if let Some(base_id) = extends_target(root, dir, &base) {
+            apply_config(root, &base_id, inherited, depth + 1)?;
+        }
+    }
+
+    let options = raw.compiler_options;
+    if let Some(base_url) = options.base_url {
+        inherited.base_url = join_path(dir, &base_url);
+    }
+    if let Some(paths) = options.paths {
+        inherited.paths = Some((paths, dir.to_string()));
+    }
+    Ok(())
+}
+
+/// Project-relative id of an `extends` target: a relative path, or a package
+/// config under `node_modules` (`@tsconfig/node20/tsconfig.json`, `my-config`)
+fn extends_target(root: &Path, dir: &str, base: &str) -> Option<String> {
+    let with_json = |path: String| -> Option<String> {
+        if root.join(&path).is_file() {
+            Some(path)
+        } else {
+            let path = format!("{}.json", path);
+            root.join(&path).is_file().then_some(path)
+        }
+    };
+
+    if base.starts_with('.') {
+        return with_json(join_path(dir, base)?);
+    }
+
+    // Packages are looked up in `node_modules` of every ancestor directory
+    let mut search = Some(dir.to_string());
+    while let Some(current) = search {
+        let modules = join_path(&current, "node_modules")?;
+        let package = join_path(&modules, base)?;
+        if let Some(found) = with_json(package.clone()).or_else(|| with_json(format!("{}/tsconfig.json", package))) {
+            return Some(found);
+        }
+        search = (!current.is_empty()).then(|| split_parent(&current).0.to_string());
+    }
+    None
+}
+
+/// Strip comments and trailing commas, which tsconfig files allow but JSON does not
+fn strip_jsonc(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let mut out: Vec<char> = Vec::with_capacity(chars.len());
+    // Position in `out` of a comma that may turn out to be trailing
+    let mut pending_comma: Option<usize> = None;
+    let mut in_string = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            out.push(c);
+            if c == '\\' && i + 1 < chars.len() {
+                out.push(chars[i + 1]);
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        match (c, chars.get(i + 1)) {
+            ('/', Some('/')) => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            }
+            ('/', Some('*')) => {
+                i += 2;
+                while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                    i += 1;
+                }
+                i += 2;
+                continue;
+            }
+            (c, _) if c.is_whitespace() => {}
+            ('}' | ']', _) => {
+                if let Some(comma) = pending_comma.take() {
+                    out[comma] = ' ';
+                }
+            }
+            (',', _) => pending_comma = Some(out.len()),
+            ('"', _) => {
+                in_string = true;
+                pending_comma = None;
+            }
+            _ => pending_comma = None,
+        }
+        out.push(c);
+        i += 1;
+    }
+    out.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_ts_configs_with_extends() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            (
+                "tsconfig.base.json",
+                r#"{
+                    // Shared by every app
+                    "compilerOptions": {
+                        "baseUrl": ".",
+                        "paths": { "@shared/*": ["libs/shared/src/*"], },
+                    },
+                }"#,
+            ),
+            ("apps/web/tsconfig.json", r#"{ "extends": "../../tsconfig.base" }"#),
+            (
+                "apps/admin/jsconfig.json",
+                r#"{ "extends": "@acme/tsconfig", "compilerOptions": { "paths": { "~/*": ["./src/*"] } } }"#,
+            ),
+            (
+                "node_modules/@acme/tsconfig/tsconfig.json",
+                r#"{ "display": "/* not a comment */", "compilerOptions": { "strict": true } }"#,
+            ),
+        ];
+        for (file, content) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+
+        let node_ids: HashSet<String> = ["apps/web/tsconfig.json", "apps/admin/jsconfig.json"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let config = ProjectConfig::load(root, &node_ids);
+
+        let web = config.ts_config_for("apps/web/src/main.ts").unwrap();
+        assert_eq!(web.base_url.as_deref(), Some(""));
+        // Inherited paths stay relative to the base config's baseUrl
+        assert_eq!(web.paths, vec![("@shared/*".to_string(), vec!["libs/shared/src/*".to_string()])]);
+
+        // Without baseUrl, paths are relative to the config declaring them
+        let admin = config.ts_config_for("apps/admin/src/app.js").unwrap();
+        assert_eq!(admin.base_url, None);
+        assert_eq!(admin.paths, vec![("~/*".to_string(), vec!["apps/admin/src/*".to_string()])]);
+        assert!(config.ts_config_for("tools/build.ts").is_none());
+    }
+
+    #[test]
+    fn test_load_bundler_and_manual_aliases() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("web")).unwrap();
+        fs::write(
+            root.join("web/vite.config.ts"),
+            "export default { resolve: { alias: { '~components': path.resolve(__dirname, 'src/components'), vue$: 'vue/dist/vue.esm.js' } } };",
+        )
+        .unwrap();
+        fs::write(
+            root.join(ENGINE_CONFIG_FILE),
+            r##"{ "aliases": { "#shared": "./shared", "#$": "shared/index.ts" } }"##,
+        )
+        .unwrap();
+
+        let node_ids: HashSet<String> = ["web/vite.config.ts".to_string()].into_iter().collect();
+        let config = ProjectConfig::load(root, &node_ids);
+
+        let finds: Vec<(&str, &str)> = config
+            .aliases_for("web/src/main.ts")
+            .map(|a| (a.find.as_str(), a.target.as_str()))
+            .collect();
+        assert_eq!(
+            finds,
+            vec![
+                ("~components", "web/src/components"),
+                ("vue", "vue/dist/vue.esm.js"),
+                ("#shared", "shared"),
+                ("#", "shared/index.ts"),
+            ]
+        );
+        assert_eq!(config.aliases_for("api/main.ts").count(), 2);
+
+        // A bare replacement redirects to a package instead of pointing into the project
+        let vue = config.aliases_for("web/src/main.ts").find(|a| a.find == "vue").unwrap();
+        assert_eq!(vue.apply("vue"), None);
+        assert_eq!(vue.redirect("vue").as_deref(), Some("vue/dist/vue.esm.js"));
+        assert_eq!(vue.redirect("vue/compiler"), None);
+    }
+}
@@ -0,0 +1,162 @@
+//! Export module for writing the graph in formats other tools read
+//! Graphviz DOT, Mermaid flowcharts and GraphML; JSON is the graph's own serialization
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use crate::graph_module::{Graph, Node};
+use crate::parser_module::ImportKind;
+
+/// Nodes worth drawing: files and packages plus anything a link points at (Go package directories)
+fn exported_nodes(graph: &Graph) -> Vec<&Node> {
+    let linked: BTreeSet<&str> = graph
+        .links
+        .iter()
+        .flat_map(|l| [l.source.as_str(), l.target.as_str()])
+        .collect();
+    graph
+        .nodes
+        .iter()
+        .filter(|n| n.node_type != "directory" || linked.contains(n.id.as_str()))
+        .collect()
+}
+
+/// Graphviz DOT: dynamic imports dashed, re-exports dotted, cycle links red
+pub fn to_dot(graph: &Graph) -> String {
+    let mut out = String::from("digraph atomicflow {\n    rankdir=LR;\n    node [shape=box];\n");
+    for node in exported_nodes(graph) {
+        let _ = writeln!(out, "    {} [label={}];", dot_string(&node.id), dot_string(&node.name));
+    }
+    for link in &graph.links {
+        let mut attributes = Vec::new();
+        match link.kind {
+            ImportKind::Dynamic => attributes.push("style=dashed"),
+            ImportKind::ReExport => attributes.push("style=dotted"),
+            _ => {}
+        }
+        if link.in_cycle {
+            attributes.push("color=red");
+        }
+        let _ = write!(out, "    {} -> {}", dot_string(&link.source), dot_string(&link.target));
+        if !attributes.is_empty() {
+            let _ = write!(out, " [{}]", attributes.join(", "));
+        }
+        out.push_str(";\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// Mermaid flowchart; node ids are numbered since paths are not valid Mermaid ids
+pub fn to_mermaid(graph: &Graph) -> String {
+    let nodes = exported_nodes(graph);
+    let index = |id: &str| nodes.iter().position(|n| n.id == id);
+
+    let mut out = String::from("flowchart LR\n");
+    for (i, node) in nodes.iter().enumerate() {
+        let _ = writeln!(out, "    n{}[\"{}\"]", i, node.id.replace('"', "#quot;"));
+    }
+    for link in &graph.links {
+        let (Some(source), Some(target)) = (index(&link.source), index(&link.target)) else {
+            continue;
+        };
+        let arrow = if link.kind == ImportKind::Dynamic { "-.->" } else { "-->" };
+        let _ = writeln!(out, "    n{} {} n{}", source, arrow, target);
+    }
+    out
+}
+
+/// GraphML with node type/layer and link kind/weight as data attributes
+pub fn to_graphml(graph: &Graph) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n",
+        "  <key id=\"layer\" for=\"node\" attr.name=\"hex_layer\" attr.type=\"string\"/>\n",
+        "  <key id=\"kind\" for=\"edge\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+        "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n",
+        "  <graph id=\"atomicflow\" edgedefault=\"directed\">\n",
+    ));
+    for node in exported_nodes(graph) {
+        let _ = writeln!(
+            out,
+            "    <node id=\"{}\"><data key=\"type\">{}</data><data key=\"layer\">{}</data></node>",
+            xml_escape(&node.id),
+            xml_escape(&node.node_type),
+            xml_escape(&node.hex_layer)
+        );
+    }
+    for link in &graph.links {
+        let kind = serde_json::to_value(link.kind).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default();
+        let _ = writeln!(
+            out,
+            "    <edge source=\"{}\" target=\"{}\"><data key=\"kind\">{}</data><data key=\"weight\">{}</data></edge>",
+            xml_escape(&link.source),
+            xml_escape(&link.target),
+            kind,
+            link.weight.unwrap_or(1)
+        );
+    }
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+fn dot_string(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph_module::Link;
+
+    fn node(id: &str, node_type: &str) -> Node {
+        Node {
+            id: id.to_string(),
+            name: id.rsplit('/').next().unwrap().to_string(),
+            node_type: node_type.to_string(),
+            path: id.to_string(),
+            hex_layer: "default".to_string(),
+            line_count: None,
+            imports: None,
+            exports: None,
+            unused_exports: None,
+            reachable: None,
+            version: None,
+            x: None,
+            y: None,
+        }
+    }
+
+    #[test]
+    fn test_export_formats() {
+        let graph = Graph {
+            nodes: vec![node("src", "directory"), node("src/a.ts", "file"), node("src/b.ts", "file")],
+            links: vec![Link {
+                source: "src/a.ts".to_string(),
+                target: "src/b.ts".to_string(),
+                weight: Some(2),
+                kind: ImportKind::Dynamic,
+                symbols: Vec::new(),
+                in_cycle: false,
+                breaks_cycle: false,
+            }],
+            cycles: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+
+        let dot = to_dot(&graph);
+        assert!(!dot.contains("\"src\" ["));
+        assert!(dot.contains("    \"src/a.ts\" -> \"src/b.ts\" [style=dashed];\n"));
+        assert!(to_mermaid(&graph).ends_with("    n0 -.-> n1\n"));
+        assert!(to_graphml(&graph).contains("<data key=\"kind\">dynamic</data><data key=\"weight\">2</data>"));
+    }
+}
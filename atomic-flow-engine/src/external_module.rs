@@ -0,0 +1,456 @@
+//! External module for third-party packages (npm, PyPI, crates.io) the project imports
+//! Lockfiles give the installed version of each package, manifests the declared one
+
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::resolver_module::split_parent;
+
+/// Node.js core modules, which may be imported without the `node:` prefix
+pub const NODE_BUILTINS: &[&str] = &[
+    "assert", "async_hooks", "buffer", "child_process", "cluster", "console", "constants", "crypto",
+    "dgram", "diagnostics_channel", "dns", "domain", "events", "fs", "http", "http2", "https",
+    "inspector", "module", "net", "os", "path", "perf_hooks", "process", "punycode", "querystring",
+    "readline", "repl", "stream", "string_decoder", "sys", "timers", "tls", "trace_events", "tty",
+    "url", "util", "v8", "vm", "wasi", "worker_threads", "zlib",
+];
+
+/// Top-level modules of the Python standard library
+pub const PYTHON_STDLIB: &[&str] = &[
+    "__future__", "abc", "aifc", "antigravity", "argparse", "array", "ast", "asynchat", "asyncio",
+    "asyncore", "atexit", "audioop", "base64", "bdb", "binascii", "bisect", "builtins", "bz2",
+    "cProfile", "calendar", "cgi", "cgitb", "chunk", "cmath", "cmd", "code", "codecs", "codeop",
+    "collections", "colorsys", "compileall", "concurrent", "configparser", "contextlib",
+    "contextvars", "copy", "copyreg", "crypt", "csv", "ctypes", "curses", "dataclasses", "datetime",
+    "dbm", "decimal", "difflib", "dis", "distutils", "doctest", "email", "encodings", "ensurepip",
+    "enum", "errno", "faulthandler", "fcntl", "filecmp", "fileinput", "fnmatch", "fractions",
+    "ftplib", "functools", "gc", "genericpath", "getopt", "getpass", "gettext", "glob", "graphlib",
+    "grp", "gzip", "hashlib", "heapq", "hmac", "html", "http", "idlelib", "imaplib", "imghdr",
+    "imp", "importlib", "inspect", "io", "ipaddress", "itertools", "json", "keyword", "lib2to3",
+    "linecache", "locale", "logging", "lzma", "mailbox", "mailcap", "marshal", "math", "mimetypes",
+    "mmap", "modulefinder", "msilib", "msvcrt", "multiprocessing", "netrc", "nis", "nntplib", "nt",
+    "ntpath", "nturl2path", "numbers", "opcode", "operator", "optparse", "os", "ossaudiodev",
+    "pathlib", "pdb", "pickle", "pickletools", "pipes", "pkgutil", "platform", "plistlib", "poplib",
+    "posix", "posixpath", "pprint", "profile", "pstats", "pty", "pwd", "py_compile", "pyclbr",
+    "pydoc", "pydoc_data", "pyexpat", "queue", "quopri", "random", "re", "readline", "reprlib",
+    "resource", "rlcompleter", "runpy", "sched", "secrets", "select", "selectors", "shelve",
+    "shlex", "shutil", "signal", "site", "smtpd", "smtplib", "sndhdr", "socket", "socketserver",
+    "spwd", "sqlite3", "sre_compile", "sre_constants", "sre_parse", "ssl", "stat", "statistics",
+    "string", "stringprep", "struct", "subprocess", "sunau", "symtable", "sys", "sysconfig",
+    "syslog", "tabnanny", "tarfile", "telnetlib", "tempfile", "termios", "textwrap", "this",
+    "threading", "time", "timeit", "tkinter", "token", "tokenize", "tomllib", "trace", "traceback",
+    "tracemalloc", "tty", "turtle", "turtledemo", "types", "typing", "unicodedata", "unittest",
+    "urllib", "uu", "uuid", "venv", "warnings", "wave", "weakref", "webbrowser", "winreg",
+    "winsound", "wsgiref", "xdrlib", "xml", "xmlrpc", "zipapp", "zipfile", "zipimport", "zlib",
+    "zoneinfo",
+];
+
+/// Lockfiles, listing the versions actually installed
+const LOCK_FILES: [(&str, Registry); 7] = [
+    ("package-lock.json", Registry::Npm),
+    ("npm-shrinkwrap.json", Registry::Npm),
+    ("yarn.lock", Registry::Npm),
+    ("pnpm-lock.yaml", Registry::Npm),
+    ("Cargo.lock", Registry::Crates),
+    ("poetry.lock", Registry::Pypi),
+    ("uv.lock", Registry::Pypi),
+];
+
+/// Manifests, listing the declared version requirements
+const MANIFEST_FILES: [(&str, Registry); 4] = [
+    ("package.json", Registry::Npm),
+    ("Cargo.toml", Registry::Crates),
+    ("pyproject.toml", Registry::Pypi),
+    ("requirements.txt", Registry::Pypi),
+];
+
+const NPM_DEPENDENCY_GROUPS: [&str; 4] = ["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"];
+const CARGO_DEPENDENCY_GROUPS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+/// Package registry an external package comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Registry {
+    Npm,
+    Pypi,
+    Crates,
+}
+
+impl Registry {
+    /// Prefix of node ids, keeping package nodes apart from project paths
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Registry::Npm => "npm",
+            Registry::Pypi => "pypi",
+            Registry::Crates => "crates",
+        }
+    }
+
+    /// Name under which the registry considers two spellings the same package
+    ///
+    /// PyPI ignores case and treats `-`, `_` and `.` alike; Cargo imports `serde-json` as `serde_json`.
+    fn normalize(self, name: &str) -> String {
+        match self {
+            Registry::Npm => name.to_string(),
+            Registry::Pypi => name.to_lowercase().replace(['-', '.'], "_"),
+            Registry::Crates => name.replace('-', "_"),
+        }
+    }
+}
+
+/// A third-party package, imported by name (`react`, `requests`, `serde`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ExternalPackage {
+    pub registry: Registry,
+    pub name: String,
+}
+
+impl ExternalPackage {
+    pub fn new(registry: Registry, name: &str) -> Self {
+        Self {
+            registry,
+            name: name.to_string(),
+        }
+    }
+
+    /// Id of the package's node (`npm:react`, `pypi:requests`, `crates:serde`)
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.registry.prefix(), self.name)
+    }
+}
+
+/// Package versions found in the project's lockfiles and manifests
+#[derive(Debug, Default)]
+pub struct PackageVersions {
+    /// (registry, directory) => normalized name => version installed
+    locked: HashMap<(Registry, String), HashMap<String, String>>,
+    /// (registry, directory) => normalized name => version requirement declared
+    declared: HashMap<(Registry, String), HashMap<String, String>>,
+}
+
+impl PackageVersions {
+    /// Read every scanned manifest and the lockfiles next to it
+    ///
+    /// Lockfiles are read from disk: projects often ignore them (`Cargo.lock`), so they
+    /// are not among the scanned files.
+    pub fn load(root: &Path, node_ids: &HashSet<String>) -> Self {
+        let mut manifests: Vec<(&str, &str, Registry)> = node_ids
+            .iter()
+            .filter_map(|id| {
+                let (dir, file) = split_parent(id);
+                let (_, registry) = MANIFEST_FILES.iter().find(|(name, _)| *name == file)?;
+                Some((dir, file, *registry))
+            })
+            .collect();
+        manifests.sort();
+
+        let mut versions = Self::default();
+        let mut lock_dirs = HashSet::new();
+        for (dir, file, registry) in manifests {
+            versions.read(root, dir, file, registry, false);
+            if !lock_dirs.insert((dir, registry)) {
+                continue;
+            }
+            for (lock, _) in LOCK_FILES.iter().filter(|(_, r)| *r == registry) {
+                if root.join(dir).join(lock).is_file() {
+                    versions.read(root, dir, lock, registry, true);
+                }
+            }
+        }
+        versions
+    }
+
+    fn read(&mut self, root: &Path, dir: &str, file: &str, registry: Registry, locked: bool) {
+        let path = root.join(dir).join(file);
+        match read_versions(&path, file) {
+            Ok(found) => {
+                let by_dir = if locked { &mut self.locked } else { &mut self.declared };
+                let names = by_dir.entry((registry, dir.to_string())).or_default();
+                for (name, version) in found {
+                    names.entry(registry.normalize(&name)).or_insert(version);
+                }
+            }
+            Err(err) => eprintln!("Warning: ignoring {}: {:#}", path.display(), err),
+        }
+    }
+
+    /// Version of `package` for `file`: the nearest lockfile listing it, else the nearest manifest
+    pub fn version_of(&self, package: &ExternalPackage, file: &str) -> Option<String> {
+        let name = package.registry.normalize(&package.name);
+        [&self.locked, &self.declared].into_iter().find_map(|by_dir| {
+            let mut dir = split_parent(file).0;
+            loop {
+                let key = (package.registry, dir.to_string());
+                if let Some(version) = by_dir.get(&key).and_then(|names| names.get(&name)) {
+                    return Some(version.clone());
+                }
+                if dir.is_empty() {
+                    return None;
+                }
+                dir = split_parent(dir).0;
+            }
+        })
+    }
+}
+
+/// (name, version) pairs listed in a lockfile or manifest
+fn read_versions(path: &Path, file_name: &str) -> Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(path)?;
+    match file_name {
+        "package-lock.json" | "npm-shrinkwrap.json" => npm_lock_versions(&content),
+        "yarn.lock" => Ok(yarn_lock_versions(&content)),
+        "pnpm-lock.yaml" => pnpm_lock_versions(&content),
+        "Cargo.lock" | "poetry.lock" | "uv.lock" => toml_lock_versions(&content),
+        "package.json" => package_json_versions(&content),
+        "Cargo.toml" => cargo_toml_versions(&content),
+        "pyproject.toml" => pyproject_versions(&content),
+        "requirements.txt" => Ok(content.lines().filter_map(requirement).collect()),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Top-level `node_modules` entries (lockfile v2+), or `dependencies` (v1)
+fn npm_lock_versions(content: &str) -> Result<Vec<(String, String)>> {
+    let lock: Value = serde_json::from_str(content).context("Invalid JSON")?;
+    let mut versions = Vec::new();
+    if let Some(packages) = lock.get("packages").and_then(Value::as_object) {
+        for (key, entry) in packages {
+            let Some(name) = key.strip_prefix("node_modules/").filter(|n| !n.contains("/node_modules/")) else {
+                continue;
+            };
+            if let Some(version) = entry.get("version").and_then(Value::as_str) {
+                versions.push((name.to_string(), version.to_string()));
+            }
+        }
+    } else if let Some(dependencies) = lock.get("dependencies").and_then(Value::as_object) {
+        for (name, entry) in dependencies {
+            if let Some(version) = entry.get("version").and_then(Value::as_str) {
+                versions.push((name.clone(), version.to_string()));
+            }
+        }
+    }
+    Ok(versions)
+}
+
+/// Entries of a yarn classic or berry lockfile:
+/// `"react@^18.0.0", "react@^18.2.0":` followed by `  version "18.2.0"` (or `version: 18.2.0`)
+fn yarn_lock_versions(content: &str) -> Vec<(String, String)> {
+    let mut versions = Vec::new();
+    let mut names: Vec<String> = Vec::new();
+    for line in content.lines() {
+        if !line.starts_with([' ', '#']) && line.ends_with(':') {
+            names = line
+                .trim_end_matches(':')
+                .split(',')
+                .filter_map(|spec| {
+                    let spec = spec.trim().trim_matches('"');
+                    // Skip the `@` of a scope
+                    let at = spec.get(1..)?.find('@')? + 1;
+                    Some(spec[..at].to_string())
+                })
+                .collect();
+            names.dedup();
+        } else if let Some(field) = line.strip_prefix("  ").filter(|l| !l.starts_with(' ')) {
+            if let Some(version) = field.strip_prefix("version") {
+                let version = version.trim_start_matches(':').trim().trim_matches('"');
+                versions.extend(names.drain(..).map(|name| (name, version.to_string())));
+            }
+        }
+    }
+    versions
+}
+
+/// Direct dependencies of every importer of a pnpm lockfile
+fn pnpm_lock_versions(content: &str) -> Result<Vec<(String, String)>> {
+    let lock: serde_yaml::Value = serde_yaml::from_str(content)?;
+    // Lockfile v5 lists the root importer's dependencies at the top level
+    let mut importers = vec![&lock];
+    if let Some(by_path) = lock.get("importers").and_then(serde_yaml::Value::as_mapping) {
+        importers.extend(by_path.values());
+    }
+
+    let mut versions = Vec::new();
+    for importer in importers {
+        for group in NPM_DEPENDENCY_GROUPS {
+            let Some(dependencies) = importer.get(group).and_then(serde_yaml::Value::as_mapping) else {
+                continue;
+            };
+            for (name, entry) in dependencies {
+                let version = entry.as_str().or_else(|| entry.get("version")?.as_str());
+                if let (Some(name), Some(version)) = (name.as_str(), version) {
+                    // Drop the peer dependency suffix: `18.2.0(react@18.2.0)`, `18.2.0_react@18.2.0`
+                    let version = version.split(['(', '_']).next().unwrap_or(version);
+                    versions.push((name.to_string(), version.to_string()));
+                }
+            }
+        }
+    }
+    Ok(versions)
+}
+
+/// `[[package]]` tables of `Cargo.lock`, `poetry.lock` and `uv.lock`
+fn toml_lock_versions(content: &str) -> Result<Vec<(String, String)>> {
+    #[derive(Deserialize, Default)]
+    #[serde(default)]
+    struct Lockfile {
+        package: Vec<LockedPackage>,
+    }
+    #[derive(Deserialize)]
+    struct LockedPackage {
+        name: String,
+        version: Option<String>,
+    }
+
+    let lock: Lockfile = toml::from_str(content)?;
+    Ok(lock
+        .package
+        .into_iter()
+        .filter_map(|package| Some((package.name, package.version?)))
+        .collect())
+}
+
+fn package_json_versions(content: &str) -> Result<Vec<(String, String)>> {
+    let manifest: Value = serde_json::from_str(content).context("Invalid JSON")?;
+    let mut versions = Vec::new();
+    for group in NPM_DEPENDENCY_GROUPS {
+        if let Some(dependencies) = manifest.get(group).and_then(Value::as_object) {
+            for (name, version) in dependencies {
+                if let Some(version) = version.as_str() {
+                    versions.push((name.clone(), version.to_string()));
+                }
+            }
+        }
+    }
+    Ok(versions)
+}
+
+/// Dependency tables of a crate and `[workspace.dependencies]`; path-only dependencies have no version
+fn cargo_toml_versions(content: &str) -> Result<Vec<(String, String)>> {
+    let manifest: toml::Value = toml::from_str(content)?;
+    let workspace = manifest.get("workspace").and_then(|w| w.get("dependencies"));
+    let tables = CARGO_DEPENDENCY_GROUPS
+        .iter()
+        .filter_map(|group| manifest.get(group))
+        .chain(workspace)
+        .filter_map(toml::Value::as_table);
+
+    let mut versions = Vec::new();
+    for table in tables {
+        for (name, dependency) in table {
+            let version = dependency.as_str().or_else(|| dependency.get("version")?.as_str());
+            if let Some(version) = version {
+                versions.push((name.clone(), version.to_string()));
+            }
+        }
+    }
+    Ok(versions)
+}
+
+/// `[project].dependencies` (PEP 621) and Poetry's dependency tables
+fn pyproject_versions(content: &str) -> Result<Vec<(String, String)>> {
+    let manifest: toml::Value = toml::from_str(content)?;
+    let mut versions: Vec<(String, String)> = manifest
+        .get("project")
+        .and_then(|p| p.get("dependencies"))
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|requirement_str| requirement(requirement_str.as_str()?))
+        .collect();
+
+    let poetry = manifest.get("tool").and_then(|t| t.get("poetry"));
+    let tables = ["dependencies", "dev-dependencies"]
+        .iter()
+        .filter_map(|group| poetry?.get(group)?.as_table());
+    for table in tables {
+        for (name, dependency) in table.iter().filter(|(name, _)| *name != "python") {
+            if let Some(version) = dependency.as_str().or_else(|| dependency.get("version")?.as_str()) {
+                versions.push((name.clone(), version.to_string()));
+            }
+        }
+    }
+    Ok(versions)
+}
+
+/// Name and version of a PEP 508 requirement (`requests[socks]==2.31.0 ; python_version > "3.8"`)
+///
+/// An exact pin (`==`) yields the bare version; anything else keeps the specifier.
+fn requirement(line: &str) -> Option<(String, String)> {
+    let line = line.split(['#', ';']).next()?.trim();
+    if line.starts_with('-') {
+        return None;
+    }
+    let end = line
+        .find(|c: char| !(c.is_alphanumeric() || matches!(c, '-' | '_' | '.')))
+        .unwrap_or(line.len());
+    let (name, rest) = line.split_at(end);
+    let mut spec = rest.trim();
+    if spec.starts_with('[') {
+        spec = spec.split_once(']')?.1.trim();
+    }
+    let spec = spec.strip_prefix("==").unwrap_or(spec).trim();
+    // Unversioned (`requests`) and direct URL (`pkg @ https://...`) requirements
+    if name.is_empty() || spec.is_empty() || spec.starts_with('@') {
+        return None;
+    }
+    Some((name.to_string(), spec.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            (
+                "package-lock.json",
+                r#"{ "packages": {
+                    "": { "name": "acme" },
+                    "node_modules/react": { "version": "18.2.0" },
+                    "node_modules/@tanstack/query": { "version": "5.8.1" },
+                    "node_modules/foo/node_modules/react": { "version": "16.0.0" }
+                } }"#,
+            ),
+            ("package.json", r#"{ "name": "acme", "workspaces": ["apps/*"] }"#),
+            ("apps/web/package.json", r#"{ "dependencies": { "react": "^18.0.0", "dayjs": "^1.11.0" } }"#),
+            ("legacy/package.json", r#"{ "dependencies": { "@babel/core": "^7.0.0" } }"#),
+            (
+                "legacy/yarn.lock",
+                "# yarn lockfile v1\n\n\"@babel/core@^7.0.0\", \"@babel/core@^7.1.0\":\n  version \"7.23.0\"\n  dependencies:\n    debug \"^4.1.0\"\n",
+            ),
+            ("Cargo.toml", "[package]\nname = \"acme\"\n\n[dependencies]\nserde-json = \"1.0\"\n"),
+            ("Cargo.lock", "version = 3\n\n[[package]]\nname = \"serde-json\"\nversion = \"1.0.108\"\n"),
+            ("py/requirements.txt", "# pinned\nRequests[socks]==2.31.0 ; python_version > '3.8'\nflask>=2.0\n-r base.txt\n"),
+        ];
+        for (file, content) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        // Lockfiles are found next to the manifests even when they are not scanned
+        let node_ids: HashSet<String> = files
+            .iter()
+            .map(|(file, _)| file.to_string())
+            .filter(|file| !LOCK_FILES.iter().any(|(lock, _)| file.ends_with(lock)))
+            .collect();
+        let versions = PackageVersions::load(root, &node_ids);
+
+        let version = |registry, name, file| versions.version_of(&ExternalPackage::new(registry, name), file);
+        // The lockfile wins over the range declared next to the importing file
+        assert_eq!(version(Registry::Npm, "react", "apps/web/src/main.ts").as_deref(), Some("18.2.0"));
+        assert_eq!(version(Registry::Npm, "dayjs", "apps/web/src/main.ts").as_deref(), Some("^1.11.0"));
+        assert_eq!(version(Registry::Npm, "dayjs", "src/main.ts"), None);
+        assert_eq!(version(Registry::Npm, "@tanstack/query", "src/main.ts").as_deref(), Some("5.8.1"));
+        assert_eq!(version(Registry::Npm, "@babel/core", "legacy/index.js").as_deref(), Some("7.23.0"));
+        assert_eq!(version(Registry::Crates, "serde_json", "src/main.rs").as_deref(), Some("1.0.108"));
+        assert_eq!(version(Registry::Pypi, "requests", "py/app.py").as_deref(), Some("2.31.0"));
+        assert_eq!(version(Registry::Pypi, "flask", "py/app.py").as_deref(), Some(">=2.0"));
+    }
+}
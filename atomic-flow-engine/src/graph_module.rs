@@ -0,0 +1,358 @@
+//! Graph module for turning a scanned project into nodes, links and their layout
+//! Walks, parses (in parallel, through the parse cache), resolves and analyzes in one pass
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::Result;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis_module::{find_cycles, find_reachable, find_unused_exports, ResolvedImports};
+use crate::cache_module::{FileStamp, ParseCache};
+use crate::config_module::ProjectConfig;
+use crate::external_module::{ExternalPackage, PackageVersions};
+use crate::layout_module::{calculate_hex_layer, LayoutConfig, SugiyamaLayout};
+use crate::parser_module::{parse_file, ImportKind, ImportSpecifier, ParseResult};
+use crate::resolver_module::Resolver;
+use crate::walker_module::{glob_matcher, walk_project};
+
+/// What a scan covers and which analyses run on it
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct ScanOptions {
+    /// Parse imports and build dependency links
+    pub deps: bool,
+    /// Extra roots for absolute imports, relative to the project
+    pub source_roots: Vec<String>,
+    /// Entry point globs; files get `reachable` set (implies `deps`)
+    pub entries: Vec<String>,
+    /// Only scan files matching these globs
+    pub include: Vec<String>,
+    /// Skip files and directories matching these globs
+    pub exclude: Vec<String>,
+    /// Add the third-party packages imported as `external` nodes (implies `deps`)
+    pub external: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Node {
+    pub id: String,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub node_type: String,
+    pub path: String,
+    /// Hexagonal architecture layer
+    pub hex_layer: String,
+    /// File line count (if file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_count: Option<usize>,
+    /// Number of imports (if file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imports: Option<usize>,
+    /// Number of exports (if file)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exports: Option<usize>,
+    /// Exports no other file imports (if file with exports)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unused_exports: Option<Vec<String>>,
+    /// Whether an entry point reaches this file (if file and entry points are set)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reachable: Option<bool>,
+    /// Locked (or else declared) version of the package (if external)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Position X (calculated by layout)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<f64>,
+    /// Position Y (calculated by layout)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<f64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Link {
+    pub source: String,
+    pub target: String,
+    /// Number of imports from source to target
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<usize>,
+    /// Kind of dependency (static or dynamic script import, re-export, stylesheet import, ...)
+    pub kind: ImportKind,
+    /// Bindings imported across this link, as written (`useAuth`, `AuthContext as Ctx`,
+    /// `type User`; `default` for default imports, `*` for namespaces)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<String>,
+    /// Whether source and target belong to the same dependency cycle
+    #[serde(default)]
+    pub in_cycle: bool,
+    /// Whether this link is in the minimal set that breaks its cycle
+    #[serde(default)]
+    pub breaks_cycle: bool,
+}
+
+/// An import into the project that matches no scanned file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Importing file
+    pub file: String,
+    /// 1-based line number of the import statement
+    pub line: usize,
+    /// The import path as written
+    pub source: String,
+    pub kind: ImportKind,
+    /// Files tried, best first
+    pub candidates: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Graph {
+    pub nodes: Vec<Node>,
+    pub links: Vec<Link>,
+    /// Circular dependencies: node ids of each strongly connected component
+    #[serde(default)]
+    pub cycles: Vec<Vec<String>>,
+    /// Unresolved imports, by file and line
+    #[serde(default)]
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// Scan the project and build its graph, reusing unchanged parse results from `cache`
+pub fn build_graph(options: &ScanOptions, root_path: &Path, mut cache: Option<&mut ParseCache>) -> Result<Graph> {
+    // Reachability and external packages need the full dependency graph
+    let deps = options.deps || !options.entries.is_empty() || options.external;
+    let mut nodes = Vec::new();
+    let mut links = Vec::new();
+    let mut cycles = Vec::new();
+    let mut diagnostics = Vec::new();
+    let mut parse_results: HashMap<String, ParseResult> = HashMap::new();
+
+    // Phase 1: Scan files and collect nodes
+    let entries = walk_project(root_path, &options.include, &options.exclude)?;
+
+    // Parse files in parallel; results keep the (sorted) walk order
+    let parsed: Vec<Option<(ParseResult, Option<FileStamp>)>> = entries
+        .par_iter()
+        .map(|entry| {
+            if !deps || entry.is_dir {
+                return None;
+            }
+            let stamp = FileStamp::of(&entry.path);
+            let cached = cache.as_deref().zip(stamp).and_then(|(cache, stamp)| cache.get(&entry.id, stamp));
+            let result = match cached {
+                Some(result) => result,
+                None => parse_file(&entry.path).ok()?,
+            };
+            Some((result, stamp))
+        })
+        .collect();
+
+    for (entry, parsed) in entries.into_iter().zip(parsed) {
+        let id = entry.id;
+        let name = entry.name;
+        
+        let node_type = if entry.is_dir {
+            "directory".to_string()
+        } else {
+            "file".to_string()
+        };
+
+        if name.is_empty() || id.is_empty() {
+            continue;
+        }
+
+        // Parse file for imports/exports if in dependency mode
+        let mut line_count = None;
+        let mut imports_count = None;
+        let mut exports_count = None;
+
+        if let Some((result, stamp)) = parsed {
+            if let (Some(cache), Some(stamp)) = (cache.as_deref_mut(), stamp) {
+                cache.insert(&id, stamp, &result);
+            }
+            line_count = Some(result.line_count);
+            imports_count = Some(result.imports.len());
+            exports_count = Some(result.exports.len());
+            
+            parse_results.insert(id.clone(), result);
+        }
+
+        let hex_layer = calculate_hex_layer(&id).to_string();
+
+        nodes.push(Node {
+            id: id.clone(),
+            name,
+            node_type,
+            path: id,
+            hex_layer,
+            line_count,
+            imports: imports_count,
+            exports: exports_count,
+            unused_exports: None,
+            reachable: None,
+            version: None,
+            x: None,
+            y: None,
+        });
+    }
+
+    if let Some(cache) = cache {
+        if let Err(err) = cache.finish_run() {
+            eprintln!("Warning: could not write parse cache: {}", err);
+        }
+    }
+
+    // Phase 2: Build dependency links
+    if deps {
+        let node_ids: HashSet<String> = nodes.iter().map(|n| n.id.clone()).collect();
+        let config = ProjectConfig::load(root_path, &node_ids);
+        let resolver = Resolver::new(&node_ids, &parse_results, &options.source_roots).with_config(config);
+        let resolved: ResolvedImports = parse_results
+            .par_iter()
+            .map(|(file_id, result)| {
+                let targets = result.imports.iter().map(|import| resolver.resolve(file_id, import)).collect();
+                (file_id.clone(), targets)
+            })
+            .collect();
+        
+        // Repeated imports of the same target are merged into one weighted link
+        let mut link_weights: BTreeMap<(String, String, ImportKind), (usize, BTreeSet<String>)> = BTreeMap::new();
+        // Third-party package => files importing it
+        let mut external: BTreeMap<ExternalPackage, BTreeSet<&str>> = BTreeMap::new();
+        
+        for (file_id, result) in &parse_results {
+            let source_id = resolver.link_source(file_id);
+            for (import, targets) in result.imports.iter().zip(&resolved[file_id]) {
+                for target in targets {
+                    if *target != source_id {
+                        let entry = link_weights.entry((source_id.clone(), target.clone(), import.kind)).or_default();
+                        entry.0 += 1;
+                        entry.1.extend(import.specifiers.iter().map(ImportSpecifier::symbol));
+                    }
+                }
+
+                if !targets.is_empty() {
+                    continue;
+                }
+                if let Some(candidates) = resolver.unresolved_candidates(file_id, import) {
+                    diagnostics.push(Diagnostic {
+                        file: file_id.clone(),
+                        line: import.line,
+                        source: import.source.clone(),
+                        kind: import.kind,
+                        candidates,
+                    });
+                } else if options.external {
+                    // `lodash` and `lodash/debounce` both link to the `lodash` package
+                    if let Some(package) = resolver.external_package(file_id, import) {
+                        let entry = link_weights.entry((source_id.clone(), package.id(), import.kind)).or_default();
+                        entry.0 += 1;
+                        entry.1.extend(import.specifiers.iter().map(ImportSpecifier::symbol));
+                        external.entry(package).or_default().insert(file_id.as_str());
+                    }
+                }
+            }
+        }
+        diagnostics.sort_by(|a, b| (&a.file, a.line, &a.source).cmp(&(&b.file, b.line, &b.source)));
+
+        if !external.is_empty() {
+            let versions = PackageVersions::load(root_path, &node_ids);
+            for (package, importers) in &external {
+                // The first importer's lockfile decides when several lockfiles list the package
+                let importer = importers.first().copied().unwrap_or_default();
+                let id = package.id();
+                nodes.push(Node {
+                    id: id.clone(),
+                    name: package.name.clone(),
+                    node_type: "external".to_string(),
+                    path: id,
+                    hex_layer: "default".to_string(),
+                    line_count: None,
+                    imports: None,
+                    exports: None,
+                    unused_exports: None,
+                    reachable: None,
+                    version: versions.version_of(package, importer),
+                    x: None,
+                    y: None,
+                });
+            }
+        }
+        
+        links = link_weights
+            .into_iter()
+            .map(|((source, target, kind), (weight, symbols))| Link {
+                source,
+                target,
+                weight: Some(weight),
+                kind,
+                symbols: symbols.into_iter().collect(),
+                in_cycle: false,
+                breaks_cycle: false,
+            })
+            .collect();
+
+        let found = find_cycles(links.iter().map(|l| (l.source.as_str(), l.target.as_str())));
+        let mut cycle_of: HashMap<&str, usize> = HashMap::new();
+        let mut breaking: HashSet<(&str, &str)> = HashSet::new();
+        for (index, cycle) in found.iter().enumerate() {
+            cycle_of.extend(cycle.nodes.iter().map(|n| (n.as_str(), index)));
+            breaking.extend(cycle.breaking_edges.iter().map(|(s, t)| (s.as_str(), t.as_str())));
+        }
+        for link in &mut links {
+            link.in_cycle = cycle_of.get(link.source.as_str()).is_some_and(|c| cycle_of.get(link.target.as_str()) == Some(c));
+            link.breaks_cycle = breaking.contains(&(link.source.as_str(), link.target.as_str()));
+        }
+        cycles = found.into_iter().map(|c| c.nodes).collect();
+
+        let mut unused = find_unused_exports(&parse_results, &resolved);
+        for node in &mut nodes {
+            node.unused_exports = unused.remove(&node.id);
+        }
+
+        if !options.entries.is_empty() {
+            let matcher = glob_matcher(&options.entries)?;
+            let entries: Vec<String> = nodes
+                .iter()
+                .filter(|n| n.node_type == "file" && matcher.is_match(&n.id))
+                .flat_map(|n| [n.id.clone(), resolver.link_source(&n.id)])
+                .collect();
+            let reached = find_reachable(
+                entries.iter().map(String::as_str),
+                links.iter().map(|l| (l.source.as_str(), l.target.as_str())),
+            );
+
+            // Go files are reached through their package directory
+            for node in nodes.iter_mut().filter(|n| n.node_type == "file") {
+                node.reachable = Some(reached.contains(&node.id) || reached.contains(&resolver.link_source(&node.id)));
+            }
+        }
+    }
+
+    // Phase 3: Calculate layout positions
+    if deps && !nodes.is_empty() {
+        let layout = SugiyamaLayout::new(LayoutConfig::default());
+        
+        let node_ids: Vec<String> = nodes.iter().map(|n| n.id.clone()).collect();
+        let edges: Vec<(String, String)> = links.iter()
+            .map(|l| (l.source.clone(), l.target.clone()))
+            .collect();
+        
+        let positions = layout.calculate_positions(&node_ids, &edges);
+        
+        // Apply positions to nodes
+        for node in &mut nodes {
+            if let Some(pos) = positions.get(&node.id) {
+                node.x = Some(pos.x);
+                node.y = Some(pos.y);
+            }
+        }
+    }
+
+    Ok(Graph {
+        nodes,
+        links,
+        cycles,
+        diagnostics,
+    })
+}
@@ -0,0 +1,30 @@
+//! AtomicFlow Engine - code analysis and visualization as a library
+//!
+//! Features:
+//! - AST parsing with oxc for import/export extraction
+//! - Dependency graph construction with petgraph
+//! - Sugiyama hierarchical layout algorithm
+//! - Hexagonal architecture layer detection
+//!
+//! Start with [`Scanner`]; the command line tool is a thin wrapper around it.
+
+mod parser_module;
+mod layout_module;
+mod resolver_module;
+mod analysis_module;
+mod walker_module;
+mod cache_module;
+mod config_module;
+mod package_module;
+mod external_module;
+mod graph_module;
+mod scanner_module;
+mod rules_module;
+pub mod export_module;
+pub mod watch_module;
+pub mod server_module;
+
+pub use graph_module::{Diagnostic, Graph, Link, Node, ScanOptions};
+pub use parser_module::ImportKind;
+pub use rules_module::{check_rules, default_rules, load_rules, ArchRule, ForbiddenLink, Violation, RULES_FILE};
+pub use scanner_module::{ProjectGraph, Scanner};
@@ -1,263 +1,353 @@
-//! AtomicFlow Engine - Rust backend for code analysis and visualization
-//! 
-//! Features:
-//! - AST parsing with SWC for import/export extraction
-//! - Dependency graph construction with petgraph
-//! - Sugiyama hierarchical layout algorithm
-//! - Hexagonal architecture layer detection
-
-mod parser_module;
-mod layout_module;
-
-use anyhow::Result;
-use clap::Parser;
-use serde::Serialize;
-use std::collections::HashMap;
-use std::path::PathBuf;
-use walkdir::WalkDir;
-
-use parser_module::{parse_file, ParseResult};
-use layout_module::{calculate_hex_layer, LayoutConfig, SugiyamaLayout};
+//! AtomicFlow Engine - command line interface
+//! Subcommands to scan, check rules, query, export and diff graphs, or serve JSON-RPC
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use atomic_flow_engine::{
+    check_rules, export_module, load_rules, server_module, watch_module, Graph, Node, ProjectGraph, ScanOptions,
+    Scanner,
+};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "AtomicFlow code analysis engine", long_about = None)]
-struct Args {
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// Number of threads used for parsing (defaults to one per core)
+    #[arg(long, value_name = "N", global = true)]
+    threads: Option<usize>,
+
+    /// Parse every file again instead of reusing results cached in .atomicflow/cache
+    #[arg(long, default_value = "false", global = true)]
+    no_cache: bool,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Scan a project and print its graph as JSON
+    Scan(ScanArgs),
+    /// Check dependency links against architecture rules; exits with status 1 on violations
+    Check(CheckArgs),
+    /// Answer questions about the dependency graph
+    Query(QueryArgs),
+    /// Write the dependency graph in another format
+    Export(ExportArgs),
+    /// Compare two graph snapshots written by `scan` and print the changes as JSON patches
+    Diff(DiffArgs),
+    /// Serve JSON-RPC 2.0 over stdin/stdout, one message per line
+    Serve,
+}
+
+/// Which project to scan and which of its files
+#[derive(clap::Args, Debug)]
+struct ProjectArgs {
     /// Path to the project directory to scan
     #[arg(short, long)]
     path: String,
-    
+
+    /// Additional source root for absolute imports, relative to the project (repeatable)
+    #[arg(long = "source-root", value_name = "DIR")]
+    source_roots: Vec<String>,
+
+    /// Only scan files matching this glob, relative to the project (repeatable)
+    #[arg(long = "include", value_name = "GLOB")]
+    include: Vec<String>,
+
+    /// Skip files and directories matching this glob, relative to the project (repeatable);
+    /// applied on top of .gitignore, .git/info/exclude and .atomicflowignore
+    #[arg(long = "exclude", value_name = "GLOB")]
+    exclude: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+struct ScanArgs {
+    #[command(flatten)]
+    project: ProjectArgs,
+
     /// Enable dependency graph mode (parse imports)
     #[arg(short, long, default_value = "false")]
     deps: bool,
+
+    /// Entry point glob, relative to the project (repeatable); marks every file
+    /// as reachable or unreachable from the matching files
+    #[arg(long = "entry", value_name = "GLOB")]
+    entries: Vec<String>,
+
+    /// Add imported third-party packages (npm, PyPI, crates.io) as `external` nodes
+    /// carrying the version from the lockfile or manifest (implies --deps)
+    #[arg(long, default_value = "false")]
+    external: bool,
+
+    /// Print a project-wide report of unused exports instead of the graph JSON
+    #[arg(long, default_value = "false", conflicts_with = "watch")]
+    unused_report: bool,
+
+    /// Keep running and stream graph changes as newline-delimited JSON patches
+    #[arg(long, default_value = "false")]
+    watch: bool,
 }
 
-#[derive(Serialize, Debug)]
-struct Node {
-    id: String,
-    name: String,
-    #[serde(rename = "type")]
-    node_type: String,
-    path: String,
-    /// Hexagonal architecture layer
-    hex_layer: String,
-    /// File line count (if file)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    line_count: Option<usize>,
-    /// Number of imports (if file)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    imports: Option<usize>,
-    /// Number of exports (if file)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    exports: Option<usize>,
-    /// Position X (calculated by layout)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    x: Option<f64>,
-    /// Position Y (calculated by layout)
-    #[serde(skip_serializing_if = "Option::is_none")]
-    y: Option<f64>,
+#[derive(clap::Args, Debug)]
+struct CheckArgs {
+    #[command(flatten)]
+    project: ProjectArgs,
+
+    /// Rules file (defaults to .python-live-rules.json in the project, then built-in rules)
+    #[arg(long, value_name = "FILE")]
+    rules: Option<PathBuf>,
+
+    /// Print violations as JSON
+    #[arg(long, default_value = "false")]
+    json: bool,
+
+    /// Also fail on imports into the project that match no file (broken relative
+    /// paths, aliases and `mod` declarations)
+    #[arg(long, default_value = "false")]
+    fail_on_unresolved: bool,
+}
+
+#[derive(clap::Args, Debug)]
+struct QueryArgs {
+    #[command(flatten)]
+    project: ProjectArgs,
+
+    #[command(subcommand)]
+    query: Query,
 }
 
-#[derive(Serialize, Debug)]
-struct Link {
-    source: String,
-    target: String,
-    /// Number of imports from source to target
-    #[serde(skip_serializing_if = "Option::is_none")]
-    weight: Option<usize>,
+#[derive(Subcommand, Debug)]
+enum Query {
+    /// Print a node with its incoming and outgoing links as JSON
+    Node { id: String },
+    /// List the nodes ID imports
+    Imports { id: String },
+    /// List the nodes importing ID
+    Dependents {
+        id: String,
+        /// Include nodes that import ID through other nodes
+        #[arg(long, default_value = "false")]
+        transitive: bool,
+    },
+    /// List dependency cycles, one per line
+    Cycles,
+    /// List exports no other file imports
+    Unused,
+    /// List files no entry point reaches
+    Unreachable {
+        /// Entry point glob, relative to the project (repeatable)
+        #[arg(long = "entry", value_name = "GLOB", required = true)]
+        entries: Vec<String>,
+    },
 }
 
-#[derive(Serialize, Debug)]
-struct Graph {
-    nodes: Vec<Node>,
-    links: Vec<Link>,
+#[derive(clap::Args, Debug)]
+struct ExportArgs {
+    #[command(flatten)]
+    project: ProjectArgs,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "dot")]
+    format: ExportFormat,
+
+    /// Write to this file instead of stdout
+    #[arg(short, long, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+enum ExportFormat {
+    Dot,
+    Mermaid,
+    Graphml,
+    Json,
+}
+
+#[derive(clap::Args, Debug)]
+struct DiffArgs {
+    /// Earlier snapshot (output of `scan --deps`)
+    old: PathBuf,
+    /// Later snapshot
+    new: PathBuf,
 }
 
 fn main() -> Result<()> {
-    let args = Args::parse();
-    let root_path = PathBuf::from(&args.path);
+    let cli = Cli::parse();
 
-    if !root_path.exists() {
-        eprintln!("Error: Path does not exist: {}", args.path);
-        std::process::exit(1);
+    if let Some(threads) = cli.threads {
+        rayon::ThreadPoolBuilder::new().num_threads(threads).build_global()?;
     }
 
-    let mut nodes = Vec::new();
-    let mut links = Vec::new();
-    let mut file_imports: HashMap<String, Vec<String>> = HashMap::new();
-    let mut parse_results: HashMap<String, ParseResult> = HashMap::new();
-
-    let walker = WalkDir::new(&root_path).into_iter();
-
-    // Phase 1: Scan files and collect nodes
-    for entry in walker.filter_entry(|e| !is_hidden(e)) {
-        let entry = entry?;
-        let path = entry.path();
-        
-        let relative_path = path.strip_prefix(&root_path).unwrap_or(path);
-        let path_str = relative_path.to_string_lossy().to_string();
-        let id = path_str.replace("\\", "/");
-        
-        let node_type = if entry.file_type().is_dir() {
-            "directory".to_string()
-        } else {
-            "file".to_string()
-        };
-
-        let name = entry.file_name().to_string_lossy().to_string();
-
-        if name.is_empty() || id.is_empty() {
-            continue;
-        }
+    match &cli.command {
+        Command::Scan(args) => scan(args, cli.no_cache),
+        Command::Check(args) => check(args, cli.no_cache),
+        Command::Query(args) => query(args, cli.no_cache),
+        Command::Export(args) => export(args, cli.no_cache),
+        Command::Diff(args) => diff(args),
+        Command::Serve => server_module::run(!cli.no_cache),
+    }
+}
 
-        // Parse file for imports/exports if in dependency mode
-        let mut line_count = None;
-        let mut imports_count = None;
-        let mut exports_count = None;
-
-        if args.deps && entry.file_type().is_file() {
-            if let Ok(result) = parse_file(path) {
-                line_count = Some(result.line_count);
-                imports_count = Some(result.imports.len());
-                exports_count = Some(result.exports.len());
-                
-                // Collect import sources for dependency graph
-                let import_sources: Vec<String> = result.imports
-                    .iter()
-                    .filter(|imp| imp.source.starts_with('.') || imp.source.starts_with('@'))
-                    .map(|imp| resolve_import_path(&id, &imp.source))
-                    .collect();
-                
-                if !import_sources.is_empty() {
-                    file_imports.insert(id.clone(), import_sources);
-                }
-                
-                parse_results.insert(id.clone(), result);
-            }
+impl ProjectArgs {
+    /// Scanner for the project; exits if the path does not exist
+    fn scanner(&self, deps: bool, entries: &[String], no_cache: bool) -> Scanner {
+        if !Path::new(&self.path).exists() {
+            eprintln!("Error: Path does not exist: {}", self.path);
+            std::process::exit(1);
         }
 
-        let hex_layer = calculate_hex_layer(&id).to_string();
-
-        nodes.push(Node {
-            id: id.clone(),
-            name,
-            node_type,
-            path: id,
-            hex_layer,
-            line_count,
-            imports: imports_count,
-            exports: exports_count,
-            x: None,
-            y: None,
-        });
+        Scanner::new(&self.path)
+            .options(ScanOptions {
+                deps,
+                source_roots: self.source_roots.clone(),
+                entries: entries.to_vec(),
+                include: self.include.clone(),
+                exclude: self.exclude.clone(),
+                ..ScanOptions::default()
+            })
+            .disk_cache(!no_cache)
     }
+}
 
-    // Phase 2: Build dependency links
-    if args.deps {
-        let node_ids: std::collections::HashSet<String> = 
-            nodes.iter().map(|n| n.id.clone()).collect();
-        
-        for (source_id, imports) in &file_imports {
-            for target_path in imports {
-                // Try to find matching node
-                let target_id = find_matching_node(target_path, &node_ids);
-                
-                if let Some(target) = target_id {
-                    links.push(Link {
-                        source: source_id.clone(),
-                        target,
-                        weight: Some(1),
-                    });
-                }
-            }
-        }
+fn scan(args: &ScanArgs, no_cache: bool) -> Result<()> {
+    // The unused export report needs the full dependency graph
+    let deps = args.deps || args.unused_report;
+    let mut scanner = args.project.scanner(deps, &args.entries, no_cache).external(args.external);
+    let graph = scanner.scan()?;
+
+    if args.unused_report {
+        print_unused_report(graph.nodes());
+    } else if args.watch {
+        // Unchanged files come from the cache, so a rebuild only re-parses what changed
+        let root = scanner.root().to_path_buf();
+        watch_module::run(&root, &args.project.exclude, graph.into_graph(), || scanner.scan().map(ProjectGraph::into_graph))?;
+    } else {
+        println!("{}", serde_json::to_string_pretty(&graph)?);
     }
 
-    // Phase 3: Calculate layout positions
-    if args.deps && !nodes.is_empty() {
-        let layout = SugiyamaLayout::new(LayoutConfig::default());
-        
-        let node_ids: Vec<String> = nodes.iter().map(|n| n.id.clone()).collect();
-        let edges: Vec<(String, String)> = links.iter()
-            .map(|l| (l.source.clone(), l.target.clone()))
-            .collect();
-        
-        let positions = layout.calculate_positions(&node_ids, &edges);
-        
-        // Apply positions to nodes
-        for node in &mut nodes {
-            if let Some(pos) = positions.get(&node.id) {
-                node.x = Some(pos.x);
-                node.y = Some(pos.y);
-            }
+    Ok(())
+}
+
+fn check(args: &CheckArgs, no_cache: bool) -> Result<()> {
+    let mut scanner = args.project.scanner(true, &[], no_cache);
+    let rules = load_rules(scanner.root(), args.rules.as_deref())?;
+    let graph = scanner.scan()?;
+    let violations = check_rules(graph.links(), &rules)?;
+    let unresolved = if args.fail_on_unresolved { graph.diagnostics() } else { &[] };
+
+    if args.json && args.fail_on_unresolved {
+        let report = serde_json::json!({ "violations": violations, "unresolved": unresolved });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if args.json {
+        println!("{}", serde_json::to_string_pretty(&violations)?);
+    } else {
+        for violation in &violations {
+            println!("{}: {} -> {}", violation.rule, violation.source, violation.target);
+        }
+        for diagnostic in unresolved {
+            println!("unresolved: {}:{} {}", diagnostic.file, diagnostic.line, diagnostic.source);
+        }
+        println!("{} violation(s) of {} rule(s)", violations.len(), rules.len());
+        if args.fail_on_unresolved {
+            println!("{} unresolved import(s)", unresolved.len());
         }
     }
 
-    let graph = Graph { nodes, links };
-    let json = serde_json::to_string_pretty(&graph)?;
-    
-    println!("{}", json);
-
+    if !violations.is_empty() || !unresolved.is_empty() {
+        std::process::exit(1);
+    }
     Ok(())
 }
 
-/// Resolve relative import path to absolute path within project
-fn resolve_import_path(from_file: &str, import_source: &str) -> String {
-    if import_source.starts_with('@') {
-        // Alias import (e.g., @/components/Button)
-        // Assume @ maps to src/
-        import_source.replace("@/", "src/")
-    } else {
-        // Relative import
-        let from_dir = PathBuf::from(from_file)
-            .parent()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_default();
-        
-        let mut result_parts: Vec<&str> = from_dir.split('/').filter(|s| !s.is_empty()).collect();
-        
-        for part in import_source.split('/') {
-            match part {
-                "." => {}
-                ".." => { result_parts.pop(); }
-                other => result_parts.push(other),
-            }
+fn query(args: &QueryArgs, no_cache: bool) -> Result<()> {
+    let entries = match &args.query {
+        Query::Unreachable { entries } => entries.as_slice(),
+        _ => &[],
+    };
+    let graph = args.project.scanner(true, entries, no_cache).scan()?;
+
+    let ids: Vec<String> = match &args.query {
+        Query::Node { id } => {
+            let Some(node) = graph.node(id) else {
+                bail!("Unknown node: {}", id);
+            };
+            let imports: Vec<_> = graph.imports_of(id).collect();
+            let dependents: Vec<_> = graph.dependents_of(id).collect();
+            let value = serde_json::json!({ "node": node, "imports": imports, "dependents": dependents });
+            println!("{}", serde_json::to_string_pretty(&value)?);
+            return Ok(());
         }
-        
-        result_parts.join("/")
+        Query::Imports { id } => graph.imports_of(id).map(|l| l.target.clone()).collect(),
+        Query::Dependents { id, transitive: false } => graph.dependents_of(id).map(|l| l.source.clone()).collect(),
+        Query::Dependents { id, transitive: true } => graph.transitive_dependents(id),
+        Query::Cycles => graph.cycles().iter().map(|c| c.join(", ")).collect(),
+        Query::Unused => {
+            print_unused_report(graph.nodes());
+            return Ok(());
+        }
+        Query::Unreachable { .. } => graph
+            .nodes()
+            .iter()
+            .filter(|n| n.reachable == Some(false))
+            .map(|n| n.id.clone())
+            .collect(),
+    };
+
+    // The same target can be linked with several kinds
+    let unique: BTreeSet<String> = ids.into_iter().collect();
+    for id in unique {
+        println!("{}", id);
     }
+    Ok(())
 }
 
-/// Find matching node for an import path
-fn find_matching_node(import_path: &str, node_ids: &std::collections::HashSet<String>) -> Option<String> {
-    // Try exact match
-    if node_ids.contains(import_path) {
-        return Some(import_path.to_string());
+fn export(args: &ExportArgs, no_cache: bool) -> Result<()> {
+    let graph = args.project.scanner(true, &[], no_cache).scan()?;
+    let output = match args.format {
+        ExportFormat::Dot => export_module::to_dot(graph.graph()),
+        ExportFormat::Mermaid => export_module::to_mermaid(graph.graph()),
+        ExportFormat::Graphml => export_module::to_graphml(graph.graph()),
+        ExportFormat::Json => serde_json::to_string_pretty(&graph)? + "\n",
+    };
+
+    match &args.output {
+        Some(file) => fs::write(file, output).with_context(|| format!("Failed to write {}", file.display()))?,
+        None => print!("{}", output),
     }
-    
-    // Try with common extensions
-    let extensions = ["", ".ts", ".tsx", ".js", ".jsx", "/index.ts", "/index.tsx", "/index.js"];
-    
-    for ext in extensions {
-        let candidate = format!("{}{}", import_path, ext);
-        if node_ids.contains(&candidate) {
-            return Some(candidate);
-        }
+    Ok(())
+}
+
+fn diff(args: &DiffArgs) -> Result<()> {
+    let read = |file: &Path| -> Result<Graph> {
+        let content = fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Not a graph snapshot: {}", file.display()))
+    };
+    let (old, new) = (read(&args.old)?, read(&args.new)?);
+
+    for patch in watch_module::diff_graphs(&old, &new) {
+        println!("{}", serde_json::to_string(&patch)?);
     }
-    
-    None
+    Ok(())
 }
 
-fn is_hidden(entry: &walkdir::DirEntry) -> bool {
-    entry.file_name()
-         .to_str()
-         .map(|s| {
-             s.starts_with('.') 
-             || s == "node_modules" 
-             || s == "target" 
-             || s == "dist" 
-             || s == "out" 
-             || s == "build"
-             || s == "__pycache__"
-         })
-         .unwrap_or(false)
+/// Print unused exports grouped by file, as plain text
+fn print_unused_report(nodes: &[Node]) {
+    let mut total = 0;
+    let mut files = 0;
+
+    let unused = nodes.iter().filter_map(|n| Some((&n.id, n.unused_exports.as_ref()?)));
+    for (file, names) in unused.filter(|(_, names)| !names.is_empty()) {
+        println!("{}", file);
+        for name in names {
+            println!("  {}", name);
+        }
+        total += names.len();
+        files += 1;
+    }
+
+    println!("{} unused export(s) in {} file(s)", total, files);
 }
@@ -0,0 +1,295 @@
+//! Package module for `package.json` manifests in npm, yarn and pnpm workspaces
+//! Maps workspace package names to their directories and entry points (`exports`, `module`, `main`)
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::resolver_module::{join_path, split_parent};
+use crate::walker_module::glob_matcher;
+
+const PACKAGE_JSON: &str = "package.json";
+const PNPM_WORKSPACE: &str = "pnpm-workspace.yaml";
+
+/// Conditions tried in `exports`, most useful first: source files before built output
+const EXPORT_CONDITIONS: [&str; 9] = [
+    "source",
+    "development",
+    "import",
+    "module",
+    "browser",
+    "default",
+    "require",
+    "node",
+    "types",
+];
+
+/// A package that belongs to a workspace of the project
+#[derive(Debug, Clone, Default)]
+pub struct WorkspacePackage {
+    pub name: String,
+    /// Package directory, relative to the project root
+    pub dir: String,
+    pub manifest: PackageManifest,
+}
+
+/// The parts of a `package.json` that matter for resolution
+#[derive(Deserialize, Debug, Clone, Default)]
+#[serde(default)]
+pub struct PackageManifest {
+    pub name: Option<String>,
+    pub main: Option<String>,
+    pub module: Option<String>,
+    pub source: Option<String>,
+    pub types: Option<String>,
+    pub exports: Option<Value>,
+    pub workspaces: Option<Workspaces>,
+}
+
+/// `workspaces` is a list of globs, or (yarn classic) an object holding them
+#[derive(Deserialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum Workspaces {
+    Globs(Vec<String>),
+    Config {
+        #[serde(default)]
+        packages: Vec<String>,
+    },
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct PnpmWorkspace {
+    packages: Vec<String>,
+}
+
+impl WorkspacePackage {
+    /// Files `subpath` (`.` or `./button`) may resolve to, best first, relative to the project
+    ///
+    /// With `exports` only exported subpaths resolve. When the targets are build output
+    /// that is not scanned, the conventional source locations are tried as well.
+    pub fn candidates(&self, subpath: &str) -> Vec<String> {
+        let mut targets = match &self.manifest.exports {
+            Some(exports) => export_targets(exports, subpath),
+            None if subpath == "." => {
+                let manifest = &self.manifest;
+                [&manifest.source, &manifest.module, &manifest.main, &manifest.types]
+                    .into_iter()
+                    .flatten()
+                    .cloned()
+                    .collect()
+            }
+            None => vec![subpath.to_string()],
+        };
+        if self.manifest.exports.is_none() || !targets.is_empty() {
+            targets.push(subpath.to_string());
+            targets.push(format!("./src/{}", subpath.trim_start_matches("./")));
+        }
+
+        targets.iter().filter_map(|target| join_path(&self.dir, target)).collect()
+    }
+}
+
+/// Packages of every workspace declared in the project (`workspaces` in a
+/// `package.json`, or a `pnpm-workspace.yaml`), sorted by name
+pub fn find_workspace_packages(root: &Path, node_ids: &HashSet<String>) -> Vec<WorkspacePackage> {
+    let mut manifests: BTreeMap<&str, PackageManifest> = BTreeMap::new();
+    for id in node_ids.iter().filter(|id| split_parent(id).1 == PACKAGE_JSON) {
+        match read_manifest(&root.join(id)) {
+            Ok(manifest) => {
+                manifests.insert(split_parent(id).0, manifest);
+            }
+            Err(err) => eprintln!("Warning: ignoring {}: {:#}", id, err),
+        }
+    }
+
+    // Workspace root directory => member globs
+    let mut workspaces: Vec<(&str, Vec<String>)> = Vec::new();
+    for (dir, manifest) in &manifests {
+        match &manifest.workspaces {
+            Some(Workspaces::Globs(globs)) | Some(Workspaces::Config { packages: globs }) => {
+                workspaces.push((dir, globs.clone()));
+            }
+            None => {}
+        }
+    }
+    for id in node_ids.iter().filter(|id| split_parent(id).1 == PNPM_WORKSPACE) {
+        let parsed = fs::read_to_string(root.join(id))
+            .map_err(anyhow::Error::from)
+            .and_then(|content| Ok(serde_yaml::from_str::<Option<PnpmWorkspace>>(&content)?));
+        match parsed {
+            Ok(workspace) => workspaces.push((split_parent(id).0, workspace.unwrap_or_default().packages)),
+            Err(err) => eprintln!("Warning: ignoring {}: {:#}", id, err),
+        }
+    }
+
+    let mut members: BTreeMap<&str, &PackageManifest> = BTreeMap::new();
+    for (workspace_dir, globs) in workspaces {
+        let (excluded, included): (Vec<String>, Vec<String>) = globs.into_iter().partition(|g| g.starts_with('!'));
+        let excluded: Vec<String> = excluded.iter().map(|g| g[1..].to_string()).collect();
+        let (Ok(included), Ok(excluded)) = (glob_matcher(&included), glob_matcher(&excluded)) else {
+            let dir = if workspace_dir.is_empty() { "." } else { workspace_dir };
+            eprintln!("Warning: invalid workspace globs in {}", dir);
+            continue;
+        };
+
+        for (dir, manifest) in &manifests {
+            let relative = match workspace_dir {
+                "" => Some(*dir),
+                _ => dir.strip_prefix(workspace_dir).and_then(|d| d.strip_prefix('/')),
+            };
+            if let Some(relative) = relative.filter(|r| !r.is_empty()) {
+                let relative = relative.trim_end_matches('/');
+                if included.is_match(relative) && !excluded.is_match(relative) {
+                    members.insert(dir, manifest);
+                }
+            }
+        }
+    }
+
+    let mut packages: Vec<WorkspacePackage> = members
+        .into_iter()
+        .filter_map(|(dir, manifest)| {
+            Some(WorkspacePackage {
+                name: manifest.name.clone()?,
+                dir: dir.to_string(),
+                manifest: manifest.clone(),
+            })
+        })
+        .collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    packages
+}
+
+/// Split a bare specifier into package name and subpath:
+/// `@acme/ui/button` => (`@acme/ui`, `./button`), `lodash` => (`lodash`, `.`)
+pub fn split_package_specifier(specifier: &str) -> Option<(&str, String)> {
+    if specifier.starts_with('.') || specifier.starts_with('/') || specifier.is_empty() {
+        return None;
+    }
+    let segments = if specifier.starts_with('@') { 2 } else { 1 };
+    let end = specifier.match_indices('/').nth(segments - 1).map(|(i, _)| i);
+    let (name, rest) = match end {
+        Some(i) => (&specifier[..i], &specifier[i + 1..]),
+        None => (specifier, ""),
+    };
+    if segments == 2 && !name.contains('/') {
+        return None;
+    }
+    let subpath = if rest.is_empty() { ".".to_string() } else { format!("./{}", rest) };
+    Some((name, subpath))
+}
+
+fn read_manifest(path: &Path) -> Result<PackageManifest> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).context("Invalid JSON")
+}
+
+/// Targets `exports` maps `subpath` to, relative to the package directory
+fn export_targets(exports: &Value, subpath: &str) -> Vec<String> {
+    let subpaths = match exports {
+        Value::Object(map) if map.keys().any(|k| k.starts_with('.')) => map,
+        // Sugar for `{ ".": exports }`
+        _ if subpath == "." => return condition_targets(exports),
+        _ => return Vec::new(),
+    };
+
+    if let Some(value) = subpaths.get(subpath) {
+        return condition_targets(value);
+    }
+
+    // Subpath patterns (`./*`, `./features/*.js`); the longest prefix wins
+    let best = subpaths
+        .iter()
+        .filter_map(|(key, value)| {
+            let (prefix, suffix) = key.split_once('*')?;
+            let captured = subpath.strip_prefix(prefix)?.strip_suffix(suffix)?;
+            Some((prefix.len(), captured, value))
+        })
+        .max_by_key(|(prefix_len, _, _)| *prefix_len);
+    match best {
+        Some((_, captured, value)) => condition_targets(value)
+            .into_iter()
+            .map(|target| target.replace('*', captured))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Every target of a (possibly nested) conditions object, preferred conditions first
+fn condition_targets(value: &Value) -> Vec<String> {
+    match value {
+        Value::String(target) => vec![target.clone()],
+        Value::Array(values) => values.iter().flat_map(condition_targets).collect(),
+        Value::Object(conditions) => {
+            let preferred = EXPORT_CONDITIONS.iter().filter_map(|c| conditions.get(*c));
+            let others = conditions
+                .iter()
+                .filter(|(key, _)| !EXPORT_CONDITIONS.contains(&key.as_str()))
+                .map(|(_, value)| value);
+            preferred.chain(others).flat_map(condition_targets).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_workspace_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let files = [
+            ("package.json", r#"{ "name": "acme", "workspaces": ["packages/*", "!packages/legacy"] }"#),
+            (
+                "packages/ui/package.json",
+                r#"{
+                    "name": "@acme/ui",
+                    "exports": {
+                        ".": { "types": "./dist/index.d.ts", "import": "./dist/index.js" },
+                        "./button": "./src/button.tsx",
+                        "./icons/*": { "import": "./src/icons/*.tsx" }
+                    }
+                }"#,
+            ),
+            ("packages/legacy/package.json", r#"{ "name": "@acme/legacy" }"#),
+            ("tools/pnpm-workspace.yaml", "packages:\n  - 'cli'\n"),
+            ("tools/cli/package.json", r#"{ "name": "acme-cli", "main": "lib/main.js" }"#),
+        ];
+        for (file, content) in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let node_ids: HashSet<String> = files.iter().map(|(file, _)| file.to_string()).collect();
+
+        let packages = find_workspace_packages(root, &node_ids);
+        let names: Vec<(&str, &str)> = packages.iter().map(|p| (p.name.as_str(), p.dir.as_str())).collect();
+        assert_eq!(names, vec![("@acme/ui", "packages/ui"), ("acme-cli", "tools/cli")]);
+
+        let ui = &packages[0];
+        assert_eq!(
+            ui.candidates("."),
+            vec![
+                "packages/ui/dist/index.js",
+                "packages/ui/dist/index.d.ts",
+                "packages/ui",
+                "packages/ui/src",
+            ]
+        );
+        assert_eq!(ui.candidates("./icons/close")[0], "packages/ui/src/icons/close.tsx");
+        // Subpaths missing from `exports` are not importable
+        assert!(ui.candidates("./internal").is_empty());
+        assert_eq!(packages[1].candidates(".")[0], "tools/cli/lib/main.js");
+
+        assert_eq!(split_package_specifier("@acme/ui/button"), Some(("@acme/ui", "./button".to_string())));
+        assert_eq!(split_package_specifier("lodash"), Some(("lodash", ".".to_string())));
+        assert_eq!(split_package_specifier("./local"), None);
+    }
+}
@@ -1,268 +1,556 @@
-//! Parser module for extracting imports/exports from TypeScript/JavaScript files
-//! Uses regex-based parsing for reliable import/export extraction
+//! Parser module for extracting imports/exports, dispatched on the file type
+//! JavaScript/TypeScript (oxc, so results match the compiler), Vue SFCs, Python, Rust, Go,
+//! C#, Java/Kotlin, their manifests, and the aliases of Vite/webpack configs
+
+mod bundler;
+mod csharp;
+mod go;
+mod jvm;
+mod python;
+mod rust;
+mod vue;
 
 use anyhow::Result;
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Argument, CallExpression, Declaration, ExportDefaultDeclaration,
+    ExportAllDeclaration, ExportNamedDeclaration, Expression, ImportDeclaration, ImportDeclarationSpecifier,
+    ImportExpression,
+    TSImportEqualsDeclaration, TSModuleReference,
+};
+use oxc_ast_visit::{walk, Visit};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 
+pub use bundler::{BundlerAlias, Replacement};
+
+/// Kind of dependency an import represents
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportKind {
+    /// Static `import`/`require` of a script module
+    Static,
+    /// Dynamic `import()` of a script module (lazy-loaded, code-split boundary)
+    Dynamic,
+    /// Re-export of another module (`export * from`, `export { x } from`)
+    #[serde(rename = "reexport")]
+    ReExport,
+    /// Stylesheet `@import`/`@use` inside a `<style>` block
+    Style,
+    /// Module declaration (Rust `mod foo;`)
+    Module,
+    /// Project-level reference between manifests (Cargo workspace members and
+    /// path dependencies, `go.mod` local replacements, `.csproj` project references)
+    Project,
+}
+
 /// Represents an import extracted from a file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportInfo {
     /// The import path (e.g., "./utils", "@/components/Button")
     pub source: String,
     /// Whether this is a default import
-    #[allow(dead_code)]
     pub is_default: bool,
     /// Whether this is a namespace import (import * as X)
-    #[allow(dead_code)]
     pub is_namespace: bool,
+    /// 1-based line number of the import statement
+    pub line: usize,
+    /// What kind of dependency this import creates
+    pub kind: ImportKind,
+    /// Bindings pulled from the source module (empty for side-effect imports,
+    /// `require` and languages without named imports)
+    pub specifiers: Vec<ImportSpecifier>,
+}
+
+/// A single binding of an import (`useAuth`, `default as App`, `* as api`, `type Props`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportSpecifier {
+    /// Name exported by the source module (`default` for default imports, `*` for namespaces)
+    pub imported: String,
+    /// Local name the binding is known by in the importing file
+    pub local: String,
+    /// Whether the binding is type-only (`import type`, `import { type X }`)
+    pub is_type: bool,
+}
+
+impl ImportSpecifier {
+    /// The binding as written in a named import (`useAuth`, `AuthContext as Ctx`, `type User`)
+    ///
+    /// Default and namespace bindings keep `default` and `*`: their local name is always new.
+    pub fn symbol(&self) -> String {
+        let name = if self.imported == "default" || self.imported == "*" || self.imported == self.local {
+            self.imported.clone()
+        } else {
+            format!("{} as {}", self.imported, self.local)
+        };
+        if self.is_type {
+            format!("type {}", name)
+        } else {
+            name
+        }
+    }
 }
 
 /// Represents an export extracted from a file
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportInfo {
     /// The exported name
-    #[allow(dead_code)]
     pub name: String,
     /// Whether this is a default export
-    #[allow(dead_code)]
     pub is_default: bool,
+    /// Module the name is re-exported from (`export { a } from './mod'`)
+    pub source: Option<String>,
+    /// Name in the source module when re-exported (`a` in `export { a as b } from`,
+    /// `*` for `export * from`)
+    pub imported: Option<String>,
 }
 
 /// Result of parsing a file
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ParseResult {
     pub imports: Vec<ImportInfo>,
     pub exports: Vec<ExportInfo>,
     pub line_count: usize,
+    /// Package names this file declares (the crate name in a `Cargo.toml`,
+    /// the module path in a `go.mod`, the root namespace of a `.csproj`,
+    /// the namespaces declared in a `.cs` file, the package of a Java/Kotlin file)
+    pub packages: Vec<String>,
+    /// Third-party packages a manifest depends on (the crates of a `Cargo.toml`)
+    #[serde(default)]
+    pub dependencies: Vec<String>,
 }
 
-/// Parse a TypeScript/JavaScript file and extract imports/exports
-/// Uses regex-based extraction for reliability
+/// Parse a source file and extract imports/exports
 pub fn parse_file(file_path: &Path) -> Result<ParseResult> {
     let content = std::fs::read_to_string(file_path)?;
     let line_count = content.lines().count();
-    
+
     // Check if it's a parseable file
     let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
-    match ext {
-        "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" | "vue" => {}
-        _ => return Ok(ParseResult { line_count, ..Default::default() }),
+    let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    let manifest = match file_name {
+        "Cargo.toml" => Some(rust::parse_manifest(&content)),
+        "go.mod" => Some(go::parse_go_mod(&content)),
+        _ if ext == "csproj" => {
+            let project_name = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+            Some(csharp::parse_project(&content, project_name))
+        }
+        _ => None,
+    };
+    if let Some((imports, packages)) = manifest {
+        let dependencies = match file_name {
+            "Cargo.toml" => rust::manifest_dependencies(&content),
+            _ => Vec::new(),
+        };
+        return Ok(ParseResult { imports, line_count, packages, dependencies, ..Default::default() });
     }
 
-    let imports = extract_imports(&content);
-    let exports = extract_exports(&content);
+    let source_type = match ext {
+        "ts" | "mts" | "cts" => SourceType::ts(),
+        "tsx" => SourceType::tsx(),
+        // Plain .js files frequently contain JSX in React projects
+        "js" | "jsx" | "mjs" => SourceType::jsx(),
+        "cjs" => SourceType::cjs(),
+        "vue" => {
+            let (imports, exports) = vue::parse_sfc(&content);
+            return Ok(ParseResult { imports, exports, line_count, ..Default::default() });
+        }
+        "py" | "pyi" => {
+            let imports = python::extract_imports(&content);
+            return Ok(ParseResult { imports, line_count, ..Default::default() });
+        }
+        "rs" => {
+            let imports = rust::extract_imports(&content);
+            return Ok(ParseResult { imports, line_count, ..Default::default() });
+        }
+        "go" => {
+            let imports = go::extract_imports(&content);
+            return Ok(ParseResult { imports, line_count, ..Default::default() });
+        }
+        "cs" => {
+            let (imports, packages) = csharp::parse_source(&content);
+            return Ok(ParseResult { imports, line_count, packages, ..Default::default() });
+        }
+        "java" | "kt" | "kts" => {
+            let (imports, packages) = jvm::parse_source(&content);
+            return Ok(ParseResult { imports, line_count, packages, ..Default::default() });
+        }
+        _ => return Ok(ParseResult { line_count, ..Default::default() }),
+    };
+
+    let (imports, exports) = parse_ecmascript(&content, source_type);
 
     Ok(ParseResult {
         imports,
         exports,
         line_count,
+        ..Default::default()
     })
 }
 
-/// Extract imports using pattern matching
-fn extract_imports(content: &str) -> Vec<ImportInfo> {
-    let mut imports = Vec::new();
-    
-    for line in content.lines() {
-        let line = line.trim();
-        
-        // Skip comments
-        if line.starts_with("//") || line.starts_with("/*") || line.starts_with("*") {
-            continue;
+/// Read the `resolve.alias` entries of a Vite or webpack config file
+pub fn parse_bundler_aliases(file_path: &Path) -> Result<Vec<BundlerAlias>> {
+    let content = std::fs::read_to_string(file_path)?;
+    let source_type = match file_path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "ts" | "mts" | "cts" => SourceType::ts(),
+        "cjs" => SourceType::cjs(),
+        _ => SourceType::mjs(),
+    };
+    Ok(bundler::extract_aliases(&content, source_type))
+}
+
+/// Parse ECMAScript/TypeScript source and collect its imports and exports
+fn parse_ecmascript(content: &str, source_type: SourceType) -> (Vec<ImportInfo>, Vec<ExportInfo>) {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, content, source_type).parse();
+
+    let mut collector = ModuleCollector {
+        lines: LineIndex::new(content),
+        imports: Vec::new(),
+        exports: Vec::new(),
+    };
+
+    // The parser recovers from most syntax errors; a panicked parse leaves an empty program
+    if !ret.panicked {
+        collector.visit_program(&ret.program);
+    }
+
+    (collector.imports, collector.exports)
+}
+
+/// Maps byte offsets to 1-based line numbers
+struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    fn new(content: &str) -> Self {
+        let mut line_starts = vec![0];
+        line_starts.extend(content.match_indices('\n').map(|(i, _)| i + 1));
+        Self { line_starts }
+    }
+
+    fn line_of(&self, offset: u32) -> usize {
+        match self.line_starts.binary_search(&(offset as usize)) {
+            Ok(line) => line + 1,
+            Err(line) => line,
         }
-        
-        // Match import statements
-        if line.starts_with("import ") {
-            if let Some(source) = extract_import_source(line) {
-                let is_default = line.contains("import ") && 
-                    !line.contains("import {") && 
-                    !line.contains("import *") &&
-                    !line.contains("import type ");
-                let is_namespace = line.contains("import * as");
-                
-                imports.push(ImportInfo {
-                    source,
-                    is_default,
-                    is_namespace,
-                });
-            }
+    }
+}
+
+/// Comment and string literal syntax of a language, for [`blank_comments`]
+struct CommentSyntax {
+    /// Characters that open and close a string or char literal
+    quotes: &'static [char],
+    /// Quote of raw strings: no escapes, may span lines (Go backticks)
+    raw_quote: Option<char>,
+    /// C# verbatim strings (`@"..."`, where `""` escapes a quote)
+    verbatim: bool,
+    /// Blank string bodies too, not only comments
+    blank_strings: bool,
+    /// `//` right after a `:` is not a comment (unquoted CSS `url(http://...)`)
+    url_slashes: bool,
+}
+
+/// C-family syntax shared by C#, Java and Kotlin; string bodies are blanked
+const C_FAMILY: CommentSyntax = CommentSyntax {
+    quotes: &['"', '\''],
+    raw_quote: None,
+    verbatim: true,
+    blank_strings: true,
+    url_slashes: false,
+};
+
+/// Replace `//` and `/* */` comments with spaces, keeping byte offsets
+///
+/// String literals are skipped so comment markers inside them survive, and are
+/// blanked as well when `syntax.blank_strings` is set.
+fn blank_comments(content: &str, syntax: &CommentSyntax) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let literal = |out: &mut String, c: char| {
+        if syntax.blank_strings {
+            blank(out, c);
+        } else {
+            out.push(c);
         }
-        
-        // Also match require() calls
-        if line.contains("require(") {
-            if let Some(source) = extract_require_source(line) {
-                imports.push(ImportInfo {
-                    source,
-                    is_default: true,
-                    is_namespace: false,
-                });
+    };
+
+    while let Some(c) = chars.next() {
+        match (c, chars.peek()) {
+            ('/', Some('/')) if !(syntax.url_slashes && out.ends_with(':')) => {
+                out.push(' ');
+                for inner in chars.by_ref() {
+                    blank(&mut out, inner);
+                    if inner == '\n' {
+                        break;
+                    }
+                }
+            }
+            ('/', Some('*')) => {
+                out.push(' ');
+                let mut prev = ' ';
+                for inner in chars.by_ref() {
+                    blank(&mut out, inner);
+                    if prev == '*' && inner == '/' {
+                        break;
+                    }
+                    prev = inner;
+                }
+            }
+            ('@', Some('"')) if syntax.verbatim => {
+                // Verbatim string: "" escapes a quote, backslashes are literal
+                literal(&mut out, c);
+                literal(&mut out, chars.next().unwrap_or('"'));
+                while let Some(inner) = chars.next() {
+                    literal(&mut out, inner);
+                    if inner == '"' {
+                        if chars.peek() == Some(&'"') {
+                            literal(&mut out, chars.next().unwrap_or('"'));
+                        } else {
+                            break;
+                        }
+                    }
+                }
             }
+            (quote, _) if syntax.quotes.contains(&quote) => {
+                let raw = syntax.raw_quote == Some(quote);
+                literal(&mut out, quote);
+                while let Some(inner) = chars.next() {
+                    literal(&mut out, inner);
+                    if inner == '\\' && !raw {
+                        if let Some(escaped) = chars.next() {
+                            literal(&mut out, escaped);
+                        }
+                    } else if inner == quote || (inner == '\n' && !raw) {
+                        break;
+                    }
+                }
+            }
+            _ => out.push(c),
         }
     }
-    
-    imports
+
+    out
 }
 
-/// Extract the source path from an import statement
-fn extract_import_source(line: &str) -> Option<String> {
-    // Find the quoted string after "from" or in the import
-    let parts: Vec<&str> = if line.contains(" from ") {
-        line.split(" from ").collect()
+/// Replace a character with spaces of the same byte width, keeping newlines
+fn blank(out: &mut String, c: char) {
+    if c == '\n' {
+        out.push('\n');
     } else {
-        // import 'module' style
-        vec![line]
-    };
-    
-    let quote_part = parts.last()?;
-    extract_quoted_string(quote_part)
+        out.extend(std::iter::repeat_n(' ', c.len_utf8()));
+    }
 }
 
-/// Extract the source path from a require() call
-fn extract_require_source(line: &str) -> Option<String> {
-    let start = line.find("require(")?;
-    let after_require = &line[start + 8..];
-    extract_quoted_string(after_require)
+/// AST visitor that records module-level imports and exports
+struct ModuleCollector {
+    lines: LineIndex,
+    imports: Vec<ImportInfo>,
+    exports: Vec<ExportInfo>,
 }
 
-/// Extract a quoted string from text
-fn extract_quoted_string(text: &str) -> Option<String> {
-    // Try single quotes
-    if let Some(start) = text.find('\'') {
-        let after_start = &text[start + 1..];
-        if let Some(end) = after_start.find('\'') {
-            return Some(after_start[..end].to_string());
-        }
+impl ModuleCollector {
+    fn push_export(&mut self, name: &str) {
+        self.exports.push(ExportInfo {
+            name: name.to_string(),
+            is_default: name == "default",
+            source: None,
+            imported: None,
+        });
     }
-    
-    // Try double quotes
-    if let Some(start) = text.find('"') {
-        let after_start = &text[start + 1..];
-        if let Some(end) = after_start.find('"') {
-            return Some(after_start[..end].to_string());
-        }
+
+    fn push_reexport(&mut self, name: &str, source: &str, imported: &str) {
+        self.exports.push(ExportInfo {
+            name: name.to_string(),
+            is_default: name == "default",
+            source: Some(source.to_string()),
+            imported: Some(imported.to_string()),
+        });
     }
-    
-    // Try backticks
-    if let Some(start) = text.find('`') {
-        let after_start = &text[start + 1..];
-        if let Some(end) = after_start.find('`') {
-            return Some(after_start[..end].to_string());
-        }
+
+    /// Re-exports also depend on the module they forward
+    fn push_reexport_import(&mut self, source: &str, specifiers: Vec<ImportSpecifier>, offset: u32) {
+        self.imports.push(ImportInfo {
+            source: source.to_string(),
+            is_default: false,
+            is_namespace: specifiers.iter().any(|s| s.imported == "*"),
+            line: self.lines.line_of(offset),
+            kind: ImportKind::ReExport,
+            specifiers,
+        });
     }
-    
-    None
 }
 
-/// Extract exports using pattern matching
-fn extract_exports(content: &str) -> Vec<ExportInfo> {
-    let mut exports = Vec::new();
-    
-    for line in content.lines() {
-        let line = line.trim();
-        
-        // Skip comments
-        if line.starts_with("//") || line.starts_with("/*") || line.starts_with("*") {
-            continue;
-        }
-        
-        // export default
-        if line.starts_with("export default ") {
-            exports.push(ExportInfo {
-                name: "default".to_string(),
+impl<'a> Visit<'a> for ModuleCollector {
+    fn visit_import_declaration(&mut self, it: &ImportDeclaration<'a>) {
+        let is_type_import = it.import_kind.is_type();
+        let specifiers: Vec<ImportSpecifier> = it
+            .specifiers
+            .iter()
+            .flatten()
+            .map(|specifier| match specifier {
+                ImportDeclarationSpecifier::ImportSpecifier(s) => ImportSpecifier {
+                    imported: s.imported.name().to_string(),
+                    local: s.local.name.to_string(),
+                    is_type: is_type_import || s.import_kind.is_type(),
+                },
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => ImportSpecifier {
+                    imported: "default".to_string(),
+                    local: s.local.name.to_string(),
+                    is_type: is_type_import,
+                },
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => ImportSpecifier {
+                    imported: "*".to_string(),
+                    local: s.local.name.to_string(),
+                    is_type: is_type_import,
+                },
+            })
+            .collect();
+
+        self.imports.push(ImportInfo {
+            source: it.source.value.to_string(),
+            is_default: specifiers.iter().any(|s| s.imported == "default"),
+            is_namespace: specifiers.iter().any(|s| s.imported == "*"),
+            line: self.lines.line_of(it.span.start),
+            kind: ImportKind::Static,
+            specifiers,
+        });
+    }
+
+    fn visit_ts_import_equals_declaration(&mut self, it: &TSImportEqualsDeclaration<'a>) {
+        // import fs = require('fs')
+        if let TSModuleReference::ExternalModuleReference(reference) = &it.module_reference {
+            self.imports.push(ImportInfo {
+                source: reference.expression.value.to_string(),
                 is_default: true,
+                is_namespace: false,
+                line: self.lines.line_of(it.span.start),
+                kind: ImportKind::Static,
+                specifiers: Vec::new(),
             });
-            continue;
         }
-        
-        // export const/let/var/function/class
-        if line.starts_with("export const ") || 
-           line.starts_with("export let ") || 
-           line.starts_with("export var ") {
-            if let Some(name) = extract_variable_name(line) {
-                exports.push(ExportInfo {
-                    name,
-                    is_default: false,
-                });
-            }
-        } else if line.starts_with("export function ") {
-            if let Some(name) = extract_function_name(line) {
-                exports.push(ExportInfo {
-                    name,
-                    is_default: false,
-                });
-            }
-        } else if line.starts_with("export class ") {
-            if let Some(name) = extract_class_name(line) {
-                exports.push(ExportInfo {
-                    name,
-                    is_default: false,
-                });
-            }
-        } else if line.starts_with("export interface ") || line.starts_with("export type ") {
-            if let Some(name) = extract_type_name(line) {
-                exports.push(ExportInfo {
-                    name,
-                    is_default: false,
+    }
+
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if it.is_require_call() {
+            let source = match it.arguments.first() {
+                Some(Argument::StringLiteral(lit)) => Some(lit.value.to_string()),
+                Some(Argument::TemplateLiteral(tpl)) => tpl.single_quasi().map(|q| q.to_string()),
+                _ => None,
+            };
+
+            if let Some(source) = source {
+                self.imports.push(ImportInfo {
+                    source,
+                    is_default: true,
+                    is_namespace: false,
+                    line: self.lines.line_of(it.span.start),
+                    kind: ImportKind::Static,
+                    specifiers: Vec::new(),
                 });
             }
         }
+
+        walk::walk_call_expression(self, it);
     }
-    
-    exports
-}
 
-fn extract_variable_name(line: &str) -> Option<String> {
-    // export const NAME = ...
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() >= 3 {
-        let name = parts[2].trim_end_matches(|c| c == ':' || c == '=' || c == ',');
-        if !name.is_empty() && name.chars().next()?.is_alphabetic() {
-            return Some(name.to_string());
+    fn visit_import_expression(&mut self, it: &ImportExpression<'a>) {
+        // import('./pages/Foo.vue'); computed specifiers cannot be resolved statically
+        let source = match &it.source {
+            Expression::StringLiteral(lit) => Some(lit.value.to_string()),
+            Expression::TemplateLiteral(tpl) => tpl.single_quasi().map(|q| q.to_string()),
+            _ => None,
+        };
+        if let Some(source) = source {
+            // The promise resolves to the module namespace object
+            self.imports.push(ImportInfo {
+                source,
+                is_default: false,
+                is_namespace: true,
+                line: self.lines.line_of(it.span.start),
+                kind: ImportKind::Dynamic,
+                specifiers: Vec::new(),
+            });
         }
+
+        walk::walk_import_expression(self, it);
     }
-    None
-}
 
-fn extract_function_name(line: &str) -> Option<String> {
-    // export function NAME(...
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() >= 3 {
-        let name = parts[2].split('(').next()?.trim();
-        if !name.is_empty() {
-            return Some(name.to_string());
-        }
+    fn visit_export_default_declaration(&mut self, it: &ExportDefaultDeclaration<'a>) {
+        self.push_export("default");
+        walk::walk_export_default_declaration(self, it);
     }
-    None
-}
 
-fn extract_class_name(line: &str) -> Option<String> {
-    // export class NAME ...
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() >= 3 {
-        let name = parts[2].trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_');
-        if !name.is_empty() {
-            return Some(name.to_string());
+    fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
+        match &it.declaration {
+            Some(Declaration::VariableDeclaration(decl)) => {
+                for declarator in &decl.declarations {
+                    for ident in declarator.id.get_binding_identifiers() {
+                        self.push_export(&ident.name);
+                    }
+                }
+            }
+            Some(decl) => {
+                if let Some(ident) = decl.id() {
+                    self.push_export(&ident.name);
+                }
+            }
+            None => match &it.source {
+                // export { a, b as c } from './mod'
+                Some(source) => {
+                    let mut specifiers = Vec::new();
+                    for specifier in &it.specifiers {
+                        self.push_reexport(&specifier.exported.name(), &source.value, &specifier.local.name());
+                        specifiers.push(ImportSpecifier {
+                            imported: specifier.local.name().to_string(),
+                            local: specifier.exported.name().to_string(),
+                            is_type: it.export_kind.is_type() || specifier.export_kind.is_type(),
+                        });
+                    }
+                    self.push_reexport_import(&source.value, specifiers, it.span.start);
+                }
+                // export { a, b as c }
+                None => {
+                    for specifier in &it.specifiers {
+                        self.push_export(&specifier.exported.name());
+                    }
+                }
+            },
         }
+
+        walk::walk_export_named_declaration(self, it);
     }
-    None
-}
 
-fn extract_type_name(line: &str) -> Option<String> {
-    // export interface/type NAME ...
-    let parts: Vec<&str> = line.split_whitespace().collect();
-    if parts.len() >= 3 {
-        let name = parts[2].trim_end_matches(|c: char| !c.is_alphanumeric() && c != '_');
-        if !name.is_empty() {
-            return Some(name.to_string());
-        }
+    fn visit_export_all_declaration(&mut self, it: &ExportAllDeclaration<'a>) {
+        // export * from './mod' forwards every named export; export * as ns binds one name
+        let name = it.exported.as_ref().map(|e| e.name().to_string());
+        let name = name.as_deref().unwrap_or("*");
+        self.push_reexport(name, &it.source.value, "*");
+
+        let specifier = ImportSpecifier {
+            imported: "*".to_string(),
+            local: name.to_string(),
+            is_type: it.export_kind.is_type(),
+        };
+        self.push_reexport_import(&it.source.value, vec![specifier], it.span.start);
     }
-    None
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn extract_imports(content: &str) -> Vec<ImportInfo> {
+        parse_ecmascript(content, SourceType::tsx()).0
+    }
+
+    fn extract_exports(content: &str) -> Vec<ExportInfo> {
+        parse_ecmascript(content, SourceType::tsx()).1
+    }
+
     #[test]
     fn test_extract_imports() {
         let content = r#"
@@ -274,9 +562,9 @@ mod tests {
         "#;
 
         let imports = extract_imports(content);
-        
+
         assert_eq!(imports.len(), 4);
-        
+
         // Assertions for specific imports
         assert!(imports.iter().any(|i| i.source == "./components" && !i.is_default && !i.is_namespace));
         assert!(imports.iter().any(|i| i.source == "react" && i.is_default));
@@ -294,12 +582,125 @@ mod tests {
         "#;
 
         let exports = extract_exports(content);
-        
+
         assert_eq!(exports.len(), 4);
-        
+
         assert!(exports.iter().any(|e| e.name == "default" && e.is_default));
         assert!(exports.iter().any(|e| e.name == "constant" && !e.is_default));
         assert!(exports.iter().any(|e| e.name == "helper" && !e.is_default));
         assert!(exports.iter().any(|e| e.name == "Config" && !e.is_default));
     }
+
+    #[test]
+    fn test_multiline_and_non_code_imports() {
+        let content = r#"/* import { fake } from "./block-comment"; */ import Real from './real';
+const tpl = `
+import { notAnImport } from './template';
+`;
+import {
+    a,
+    b,
+} from './multi';
+
+@Component({ selector: 'app' })
+export class Widget {}
+"#;
+
+        let imports = extract_imports(content);
+
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].source, "./real");
+        assert_eq!(imports[0].line, 1);
+        assert_eq!(imports[1].source, "./multi");
+        assert_eq!(imports[1].line, 5);
+
+        let exports = extract_exports(content);
+        assert!(exports.iter().any(|e| e.name == "Widget"));
+    }
+
+    #[test]
+    fn test_dynamic_imports() {
+        let content = r#"
+            const routes = [
+                { path: '/foo', component: () => import('./pages/Foo.vue') },
+                { path: '/bar', component: () => import(`./pages/Bar.vue`) },
+                { path: '/dyn', component: () => import(`./pages/${name}.vue`) },
+            ];
+        "#;
+
+        let imports = extract_imports(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.kind, i.line)).collect();
+
+        assert_eq!(
+            found,
+            vec![("./pages/Foo.vue", ImportKind::Dynamic, 3), ("./pages/Bar.vue", ImportKind::Dynamic, 4)]
+        );
+    }
+
+    #[test]
+    fn test_reexports() {
+        let content = r#"
+            export * from './models';
+            export * as api from './api';
+            export { useAuth, AuthContext as Context } from './auth';
+        "#;
+
+        let (imports, exports) = parse_ecmascript(content, SourceType::ts());
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.kind, i.is_namespace)).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("./models", ImportKind::ReExport, true),
+                ("./api", ImportKind::ReExport, true),
+                ("./auth", ImportKind::ReExport, false),
+            ]
+        );
+
+        let reexports: Vec<_> = exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.source.as_deref(), e.imported.as_deref()))
+            .collect();
+        assert_eq!(
+            reexports,
+            vec![
+                ("*", Some("./models"), Some("*")),
+                ("api", Some("./api"), Some("*")),
+                ("useAuth", Some("./auth"), Some("useAuth")),
+                ("Context", Some("./auth"), Some("AuthContext")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_import_specifiers() {
+        let content = r#"
+            import App, { useAuth, AuthContext as Ctx, type User } from './auth';
+            import type { Props } from './types';
+            import * as api from './api';
+        "#;
+
+        let imports = extract_imports(content);
+        let specifiers: Vec<Vec<_>> = imports
+            .iter()
+            .map(|i| i.specifiers.iter().map(|s| (s.imported.as_str(), s.local.as_str(), s.is_type)).collect())
+            .collect();
+
+        assert_eq!(
+            specifiers,
+            vec![
+                vec![
+                    ("default", "App", false),
+                    ("useAuth", "useAuth", false),
+                    ("AuthContext", "Ctx", false),
+                    ("User", "User", true),
+                ],
+                vec![("Props", "Props", true)],
+                vec![("*", "api", false)],
+            ]
+        );
+        let symbols: Vec<String> = imports[0].specifiers.iter().map(ImportSpecifier::symbol).collect();
+        assert_eq!(symbols, vec!["default", "useAuth", "AuthContext as Ctx", "type User"]);
+        assert!(imports[0].is_default && imports[2].is_namespace);
+    }
 }
@@ -0,0 +1,312 @@
+//! Alias extraction from Vite and webpack config files
+//! Only statically analyzable values count: literals, `path.resolve(__dirname, ...)`,
+//! `fileURLToPath(new URL(..., import.meta.url))` and constants holding them;
+//! bare specifiers (`vue/dist/vue.esm.js`) redirect to another package
+
+use std::collections::HashMap;
+
+use oxc_allocator::Allocator;
+use oxc_ast::ast::{
+    Argument, ArrayExpressionElement, Expression, ObjectProperty, ObjectPropertyKind, VariableDeclarator,
+};
+use oxc_ast_visit::{walk, Visit};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+/// An alias declared under `resolve.alias`
+#[derive(Debug, Clone, PartialEq)]
+pub struct BundlerAlias {
+    /// Specifier or specifier prefix (`@`, `~components`, `#shared`)
+    pub find: String,
+    /// Only the exact specifier matches (webpack `key$`)
+    pub exact: bool,
+    pub replacement: Replacement,
+}
+
+/// What an alias maps its specifier to
+#[derive(Debug, Clone, PartialEq)]
+pub enum Replacement {
+    /// Path relative to the config file's directory
+    Path(String),
+    /// Specifier of another package (`vue/dist/vue.esm.js`)
+    Package(String),
+}
+
+/// A statically known string
+#[derive(Clone)]
+struct PathValue {
+    path: String,
+    /// Relative or root-absolute, or built from `__dirname`, `import.meta.url` or
+    /// `process.cwd()`; anything else is a bare specifier
+    anchored: bool,
+}
+
+impl PathValue {
+    fn anchored(path: impl Into<String>) -> Self {
+        Self { path: path.into(), anchored: true }
+    }
+}
+
+/// Statically known value of a constant
+enum Value {
+    Path(PathValue),
+    Aliases(Vec<BundlerAlias>),
+}
+
+/// Aliases declared anywhere in a config file (`resolve.alias`, `configureWebpack.resolve.alias`)
+pub fn extract_aliases(content: &str, source_type: SourceType) -> Vec<BundlerAlias> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, content, source_type).parse();
+
+    let mut collector = AliasCollector {
+        constants: HashMap::new(),
+        aliases: Vec::new(),
+    };
+    if !ret.panicked {
+        collector.visit_program(&ret.program);
+    }
+    collector.aliases
+}
+
+struct AliasCollector {
+    /// `const name = ...` bindings seen so far, to follow `alias: aliases`
+    constants: HashMap<String, Value>,
+    aliases: Vec<BundlerAlias>,
+}
+
+impl AliasCollector {
+    /// Entries of an alias object (`{ '@': ... }`) or Vite array (`[{ find, replacement }]`)
+    fn aliases_of(&self, expr: &Expression) -> Option<Vec<BundlerAlias>> {
+        match expr.get_inner_expression() {
+            Expression::ObjectExpression(object) => {
+                let mut aliases = Vec::new();
+                for property in &object.properties {
+                    match property {
+                        ObjectPropertyKind::ObjectProperty(property) => {
+                            let Some(key) = property.key.static_name() else {
+                                continue;
+                            };
+                            let Some(replacement) = self.replacement_of(&property.value) else {
+                                continue;
+                            };
+                            let (find, exact) = match key.strip_suffix('$') {
+                                Some(find) => (find.to_string(), true),
+                                None => (key.to_string(), false),
+                            };
+                            aliases.push(BundlerAlias { find, exact, replacement });
+                        }
+                        ObjectPropertyKind::SpreadProperty(spread) => {
+                            aliases.extend(self.aliases_of(&spread.argument).unwrap_or_default());
+                        }
+                    }
+                }
+                Some(aliases)
+            }
+            Expression::ArrayExpression(array) => {
+                let entries = array.elements.iter().filter_map(|element| match element {
+                    ArrayExpressionElement::ObjectExpression(entry) => Some(entry),
+                    _ => None,
+                });
+                let mut aliases = Vec::new();
+                for entry in entries {
+                    let field = |name: &str| {
+                        entry.properties.iter().find_map(|property| match property {
+                            ObjectPropertyKind::ObjectProperty(p) if p.key.static_name().as_deref() == Some(name) => {
+                                Some(&p.value)
+                            }
+                            _ => None,
+                        })
+                    };
+                    // Regular expression `find`s cannot be matched statically
+                    let find = match field("find").map(Expression::get_inner_expression) {
+                        Some(Expression::StringLiteral(lit)) => lit.value.to_string(),
+                        _ => continue,
+                    };
+                    if let Some(replacement) = field("replacement").and_then(|value| self.replacement_of(value)) {
+                        aliases.push(BundlerAlias { find, exact: false, replacement });
+                    }
+                }
+                Some(aliases)
+            }
+            Expression::Identifier(ident) => match self.constants.get(ident.name.as_str()) {
+                Some(Value::Aliases(aliases)) => Some(aliases.clone()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Path or package an alias value points at
+    fn replacement_of(&self, expr: &Expression) -> Option<Replacement> {
+        let value = self.path_of(expr)?;
+        if value.anchored {
+            // Vite serves `/src` from the project root, which holds the config
+            Some(Replacement::Path(value.path.trim_start_matches('/').to_string()))
+        } else {
+            Some(Replacement::Package(value.path))
+        }
+    }
+
+    /// String an expression evaluates to; anchored paths are relative to the config file's directory
+    fn path_of(&self, expr: &Expression) -> Option<PathValue> {
+        let value = match expr.get_inner_expression() {
+            Expression::StringLiteral(lit) => PathValue {
+                path: lit.value.to_string(),
+                anchored: lit.value.starts_with('.') || lit.value.starts_with('/'),
+            },
+            Expression::TemplateLiteral(tpl) => {
+                let mut path = String::new();
+                let mut anchored = None;
+                for (i, quasi) in tpl.quasis.iter().enumerate() {
+                    let text = quasi.value.cooked.as_ref()?.as_str();
+                    path.push_str(text);
+                    if anchored.is_none() && !text.is_empty() {
+                        anchored = Some(text.starts_with('.') || text.starts_with('/'));
+                    }
+                    if let Some(expr) = tpl.expressions.get(i) {
+                        let part = self.path_of(expr)?;
+                        anchored.get_or_insert(part.anchored);
+                        path.push_str(&part.path);
+                    }
+                }
+                PathValue { path, anchored: anchored.unwrap_or(false) }
+            }
+            Expression::Identifier(ident) if ident.name == "__dirname" => PathValue::anchored("."),
+            Expression::Identifier(ident) => match self.constants.get(ident.name.as_str()) {
+                Some(Value::Path(value)) => value.clone(),
+                _ => return None,
+            },
+            Expression::CallExpression(call) => {
+                let callee = match call.callee.get_inner_expression() {
+                    Expression::Identifier(ident) => ident.name.as_str(),
+                    // `require.resolve('pkg')` looks a package up rather than building a path
+                    Expression::StaticMemberExpression(member) if !member.object.is_specific_id("require") => {
+                        member.property.name.as_str()
+                    }
+                    _ => return None,
+                };
+                match callee {
+                    // path.resolve(__dirname, 'src'), path.join(__dirname, 'src')
+                    "resolve" | "join" => {
+                        let parts = call
+                            .arguments
+                            .iter()
+                            .map(|arg| self.path_of(arg.as_expression()?))
+                            .collect::<Option<Vec<_>>>()?;
+                        // `resolve` starts from the working directory, which is the project root
+                        let anchored = callee == "resolve" || parts.first().is_some_and(|p| p.anchored);
+                        let path = parts.into_iter().map(|p| p.path).collect::<Vec<_>>().join("/");
+                        PathValue { path, anchored }
+                    }
+                    // fileURLToPath(new URL('./src', import.meta.url))
+                    "fileURLToPath" => self.path_of(call.arguments.first()?.as_expression()?)?,
+                    // process.cwd() is the project root when the bundler runs
+                    "cwd" => PathValue::anchored("."),
+                    _ => return None,
+                }
+            }
+            Expression::NewExpression(new) if new.callee.is_specific_id("URL") => {
+                match (new.arguments.first(), new.arguments.get(1)) {
+                    (Some(Argument::StringLiteral(lit)), Some(Argument::StaticMemberExpression(base)))
+                        if base.property.name == "url" =>
+                    {
+                        PathValue::anchored(lit.value.as_str())
+                    }
+                    _ => return None,
+                }
+            }
+            // new URL('./src', import.meta.url).pathname
+            Expression::StaticMemberExpression(member) if member.property.name == "pathname" => {
+                self.path_of(&member.object)?
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl<'a> Visit<'a> for AliasCollector {
+    fn visit_variable_declarator(&mut self, it: &VariableDeclarator<'a>) {
+        if let (Some(name), Some(init)) = (it.id.get_identifier_name(), &it.init) {
+            let value = match self.path_of(init) {
+                Some(value) => Some(Value::Path(value)),
+                None => self.aliases_of(init).map(Value::Aliases),
+            };
+            if let Some(value) = value {
+                self.constants.insert(name.to_string(), value);
+            }
+        }
+
+        walk::walk_variable_declarator(self, it);
+    }
+
+    fn visit_object_property(&mut self, it: &ObjectProperty<'a>) {
+        if it.key.static_name().as_deref() == Some("alias") {
+            self.aliases.extend(self.aliases_of(&it.value).unwrap_or_default());
+        }
+
+        walk::walk_object_property(self, it);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alias(find: &str, exact: bool, path: &str) -> BundlerAlias {
+        BundlerAlias {
+            find: find.to_string(),
+            exact,
+            replacement: Replacement::Path(path.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_extract_vite_and_webpack_aliases() {
+        let vite = r#"
+import { fileURLToPath, URL } from 'node:url';
+import path from 'node:path';
+import { defineConfig } from 'vite';
+
+const shared = path.resolve(__dirname, '../shared');
+
+export default defineConfig({
+  resolve: {
+    alias: [
+      { find: '@', replacement: fileURLToPath(new URL('./src', import.meta.url)) },
+      { find: '#shared', replacement: shared },
+      { find: /^~(.*)$/, replacement: '$1' },
+    ],
+  },
+});
+"#;
+        assert_eq!(
+            extract_aliases(vite, SourceType::ts()),
+            vec![alias("@", false, "./src"), alias("#shared", false, "./../shared")]
+        );
+
+        let webpack = r#"
+const path = require('path');
+const aliases = {
+  '~components': path.join(__dirname, 'src', 'components'),
+  vue$: 'vue/dist/vue.esm.js',
+  lodash: require.resolve('lodash-es'),
+};
+module.exports = {
+  resolve: { alias: { ...aliases, '@': `${__dirname}/src`, dynamic: process.env.DIR } },
+};
+"#;
+        assert_eq!(
+            extract_aliases(webpack, SourceType::cjs()),
+            vec![
+                alias("~components", false, "./src/components"),
+                BundlerAlias {
+                    find: "vue".to_string(),
+                    exact: true,
+                    replacement: Replacement::Package("vue/dist/vue.esm.js".to_string()),
+                },
+                alias("@", false, "./src"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,282 @@
+//! C# namespace and project reference extraction
+//! Reads `using` directives and `namespace` declarations from `.cs` files and
+//! `<ProjectReference>` entries from `.csproj` files
+
+use super::{blank, blank_comments, ImportInfo, ImportKind, LineIndex, C_FAMILY};
+
+/// Extract `using` directives and declared namespaces from C# source
+///
+/// Imports carry the namespace (or, for `using static` and aliases, the type)
+/// they name. Block-scoped namespaces nest, so `namespace A { namespace B {} }`
+/// declares both `A` and `A.B`.
+pub fn parse_source(content: &str) -> (Vec<ImportInfo>, Vec<String>) {
+    let code = blank_directives_and_attributes(&blank_comments(content, &C_FAMILY));
+    let lines = LineIndex::new(content);
+    let mut imports = Vec::new();
+    let mut namespaces: Vec<String> = Vec::new();
+    // Enclosing block namespaces with the brace depth their body opened at
+    let mut scopes: Vec<(String, usize)> = Vec::new();
+    let mut depth = 0usize;
+    let mut offset = 0;
+
+    for statement in code.split_inclusive([';', '{', '}']) {
+        let start = offset + (statement.len() - statement.trim_start().len());
+        offset += statement.len();
+        let text = statement.trim();
+
+        if text == "}" {
+            depth = depth.saturating_sub(1);
+            if scopes.last().is_some_and(|(_, d)| *d == depth + 1) {
+                scopes.pop();
+            }
+            continue;
+        }
+
+        let is_block = text.ends_with('{');
+        let body = text.trim_end_matches([';', '{']).trim();
+
+        if let Some(name) = body.strip_prefix("namespace ") {
+            let name = name.trim();
+            let full = match scopes.last() {
+                Some((outer, _)) => format!("{}.{}", outer, name),
+                None => name.to_string(),
+            };
+            if is_qualified_name(name) {
+                namespaces.push(full.clone());
+                if is_block {
+                    scopes.push((full, depth + 1));
+                } else {
+                    // File-scoped namespace: applies to the rest of the file
+                    scopes.push((full, 0));
+                }
+            }
+        } else if !is_block {
+            if let Some(target) = using_directive(body) {
+                imports.push(ImportInfo {
+                    source: target.to_string(),
+                    is_default: false,
+                    is_namespace: false,
+                    line: lines.line_of(start as u32),
+                    kind: ImportKind::Static,
+                    specifiers: Vec::new(),
+                });
+            }
+        }
+
+        if is_block {
+            depth += 1;
+        }
+    }
+
+    namespaces.dedup();
+    (imports, namespaces)
+}
+
+/// Blank preprocessor lines (`#nullable enable`, `#region`) and attribute sections
+/// (`[assembly: ...]`) that start a statement; neither ends in `;`, `{` or `}`, so
+/// they would otherwise run into the statement after them
+fn blank_directives_and_attributes(code: &str) -> String {
+    let mut out = String::with_capacity(code.len());
+    let mut chars = code.chars().peekable();
+    let mut line_start = true;
+    // Last non-whitespace character kept, to tell where statements start
+    let mut prev = ';';
+
+    while let Some(c) = chars.next() {
+        if c == '#' && line_start {
+            blank(&mut out, c);
+            while let Some(inner) = chars.next_if(|inner| *inner != '\n') {
+                blank(&mut out, inner);
+            }
+            continue;
+        }
+        if c == '[' && matches!(prev, ';' | '{' | '}') {
+            blank(&mut out, c);
+            let mut depth = 1;
+            for inner in chars.by_ref() {
+                blank(&mut out, inner);
+                match inner {
+                    '[' => depth += 1,
+                    ']' => depth -= 1,
+                    _ => {}
+                }
+                if depth == 0 {
+                    break;
+                }
+            }
+            line_start = false;
+            continue;
+        }
+
+        out.push(c);
+        if c == '\n' {
+            line_start = true;
+        } else if !c.is_whitespace() {
+            line_start = false;
+            prev = c;
+        }
+    }
+
+    out
+}
+
+/// Target of a `using` directive (`using A.B;`, `global using static A.B.C;`,
+/// `using Alias = A.B;`), ignoring `using` statements and declarations
+fn using_directive(statement: &str) -> Option<&str> {
+    let rest = statement.strip_prefix("global ").unwrap_or(statement);
+    let rest = rest.strip_prefix("using ")?.trim();
+    let rest = rest.strip_prefix("static ").unwrap_or(rest).trim();
+
+    let target = match rest.split_once('=') {
+        // The alias must be a single identifier (rules out `using var x = ...`)
+        Some((alias, target)) if is_identifier(alias.trim()) => target.trim(),
+        Some(_) => return None,
+        None => rest,
+    };
+    let target = target.strip_prefix("global::").unwrap_or(target);
+
+    is_qualified_name(target).then_some(target)
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_' || c == '@')
+        && text.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '@')
+}
+
+fn is_qualified_name(text: &str) -> bool {
+    !text.is_empty() && text.split('.').all(is_identifier)
+}
+
+/// Read a `.csproj`: `<ProjectReference Include="...">` entries as project-level imports
+/// and the root namespace the project declares (defaulting to the project name)
+pub fn parse_project(content: &str, project_name: &str) -> (Vec<ImportInfo>, Vec<String>) {
+    let lines = LineIndex::new(content);
+    let mut imports = Vec::new();
+    let mut pos = 0;
+
+    while let Some(rel) = content[pos..].find("<ProjectReference") {
+        let start = pos + rel;
+        let tag_end = content[start..].find('>').map(|e| start + e).unwrap_or(content.len());
+        pos = tag_end;
+
+        if let Some(include) = xml_attr(&content[start..tag_end], "Include") {
+            imports.push(ImportInfo {
+                source: include.replace('\\', "/"),
+                is_default: false,
+                is_namespace: false,
+                line: lines.line_of(start as u32),
+                kind: ImportKind::Project,
+                specifiers: Vec::new(),
+            });
+        }
+    }
+
+    let root_namespace = xml_element(content, "RootNamespace")
+        .or_else(|| xml_element(content, "AssemblyName"))
+        .unwrap_or(project_name);
+
+    (imports, vec![root_namespace.to_string()])
+}
+
+/// Quoted value of attribute `name` (`Include="..."`, `Include = '...'`)
+fn xml_attr<'s>(tag: &'s str, name: &str) -> Option<&'s str> {
+    let mut pos = 0;
+    while let Some(rel) = tag[pos..].find(name) {
+        let start = pos + rel;
+        pos = start + name.len();
+        let before_ok = tag[..start].chars().last().is_none_or(|c| c.is_whitespace());
+        let Some(value) = tag[pos..].trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let value = value.trim_start();
+        let quote = value.chars().next().filter(|q| *q == '"' || *q == '\'');
+        if let (true, Some(quote)) = (before_ok, quote) {
+            let body = &value[1..];
+            return body.find(quote).map(|end| &body[..end]);
+        }
+    }
+    None
+}
+
+fn xml_element<'s>(content: &'s str, name: &str) -> Option<&'s str> {
+    let open = format!("<{}>", name);
+    let start = content.find(&open)? + open.len();
+    let end = content[start..].find("</")?;
+    Some(content[start..start + end].trim()).filter(|v| !v.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csharp_source() {
+        let content = r#"
+global using System.Linq;
+using Acme.Billing.Models;
+using static Acme.Shared.Guard;
+using Json = System.Text.Json.JsonSerializer;
+// using Commented.Out;
+
+namespace Acme.Billing
+{
+    namespace Services
+    {
+        public class InvoiceService
+        {
+            public void Run()
+            {
+                using var scope = Create();
+                var s = "using Not.Real;";
+            }
+        }
+    }
+}
+"#;
+
+        let (imports, namespaces) = parse_source(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("System.Linq", 2),
+                ("Acme.Billing.Models", 3),
+                ("Acme.Shared.Guard", 4),
+                ("System.Text.Json.JsonSerializer", 5),
+            ]
+        );
+        assert_eq!(namespaces, vec!["Acme.Billing", "Acme.Billing.Services"]);
+    }
+
+    #[test]
+    fn test_file_scoped_namespace_and_project() {
+        let content = r#"#nullable enable
+#region Usings
+[assembly: InternalsVisibleTo("Acme.Web.Tests")]
+[assembly: CLSCompliant(false)]
+using Acme.Core;
+#endregion
+namespace Acme.Web;
+
+[Serializable]
+public class Startup {}
+"#;
+        let (imports, namespaces) = parse_source(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+        assert_eq!(found, vec![("Acme.Core", 5)]);
+        assert_eq!(namespaces, vec!["Acme.Web"]);
+
+        let csproj = r#"<Project Sdk="Microsoft.NET.Sdk">
+  <ItemGroup>
+    <ProjectReference Include = "..\Acme.Shared\Acme.Shared.csproj" />
+  </ItemGroup>
+</Project>"#;
+        let (imports, packages) = parse_project(csproj, "Acme.Web");
+
+        assert_eq!(imports.len(), 1);
+        assert_eq!((imports[0].source.as_str(), imports[0].line), ("../Acme.Shared/Acme.Shared.csproj", 3));
+        assert_eq!(imports[0].kind, ImportKind::Project);
+        assert_eq!(packages, vec!["Acme.Web"]);
+    }
+}
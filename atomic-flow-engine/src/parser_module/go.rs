@@ -0,0 +1,176 @@
+//! Go import extraction
+//! Reads `import` declarations from `.go` files and the module path from `go.mod`
+
+use super::{blank_comments, CommentSyntax, ImportInfo, ImportKind, LineIndex};
+
+/// Go strings are kept: import paths are read from them
+const GO: CommentSyntax = CommentSyntax {
+    quotes: &['"', '\'', '`'],
+    raw_quote: Some('`'),
+    verbatim: false,
+    blank_strings: false,
+    url_slashes: false,
+};
+
+/// Extract imported package paths from Go source
+///
+/// Handles single imports and `import ( ... )` groups, including aliased,
+/// blank (`_`) and dot imports; commented-out imports are ignored.
+pub fn extract_imports(content: &str) -> Vec<ImportInfo> {
+    let code = blank_comments(content, &GO);
+    let lines = LineIndex::new(content);
+    let mut imports = Vec::new();
+    let mut pos = 0;
+
+    while let Some(rel) = code[pos..].find("import") {
+        let start = pos + rel;
+        pos = start + "import".len();
+
+        // Import declarations start a line (gofmt) or follow a `;`
+        let line_start = code[..start].rfind(['\n', ';']).map(|i| i + 1).unwrap_or(0);
+        let preceded_ok = code[line_start..start].trim().is_empty();
+        let rest = &code[pos..];
+        let followed_ok = rest.starts_with(|c: char| c.is_whitespace() || c == '(');
+        if !preceded_ok || !followed_ok {
+            continue;
+        }
+
+        let trimmed = rest.trim_start();
+        let body_start = pos + (rest.len() - trimmed.len());
+
+        let (body, body_offset) = if let Some(group) = trimmed.strip_prefix('(') {
+            let end = group.find(')').unwrap_or(group.len());
+            (&group[..end], body_start + 1)
+        } else {
+            let end = trimmed.find(['\n', ';']).unwrap_or(trimmed.len());
+            (&trimmed[..end], body_start)
+        };
+
+        for (offset, path) in quoted_paths(body) {
+            imports.push(ImportInfo {
+                source: path,
+                is_default: false,
+                is_namespace: false,
+                line: lines.line_of((body_offset + offset) as u32),
+                kind: ImportKind::Static,
+                specifiers: Vec::new(),
+            });
+        }
+        pos = body_offset + body.len();
+    }
+
+    imports
+}
+
+/// Read a `go.mod`: the module path it declares, plus local `replace` targets
+/// as project-level imports
+pub fn parse_go_mod(content: &str) -> (Vec<ImportInfo>, Vec<String>) {
+    let mut imports = Vec::new();
+    let mut packages = Vec::new();
+    let mut in_replace_block = false;
+
+    for (idx, raw) in content.lines().enumerate() {
+        let line = raw.split("//").next().unwrap_or("").trim();
+
+        if let Some(module) = line.strip_prefix("module ") {
+            packages.push(module.trim().trim_matches('"').to_string());
+            continue;
+        }
+
+        let replacement = if line == "replace (" {
+            in_replace_block = true;
+            continue;
+        } else if in_replace_block && line == ")" {
+            in_replace_block = false;
+            continue;
+        } else if let Some(spec) = line.strip_prefix("replace ") {
+            spec
+        } else if in_replace_block {
+            line
+        } else {
+            continue;
+        };
+
+        // replace example.com/x => ../x
+        if let Some((_, target)) = replacement.split_once("=>") {
+            let target = target.split_whitespace().next().unwrap_or("");
+            if target.starts_with("./") || target.starts_with("../") {
+                imports.push(ImportInfo {
+                    source: target.to_string(),
+                    is_default: false,
+                    is_namespace: false,
+                    line: idx + 1,
+                    kind: ImportKind::Project,
+                    specifiers: Vec::new(),
+                });
+            }
+        }
+    }
+
+    (imports, packages)
+}
+
+/// Interpreted or raw string literals in an import body, with their byte offsets
+fn quoted_paths(body: &str) -> Vec<(usize, String)> {
+    let mut paths = Vec::new();
+    let mut pos = 0;
+
+    while let Some(rel) = body[pos..].find(['"', '`']) {
+        let start = pos + rel;
+        let quote = &body[start..start + 1];
+        let Some(len) = body[start + 1..].find(quote) else {
+            break;
+        };
+        paths.push((start, body[start + 1..start + 1 + len].to_string()));
+        pos = start + len + 2;
+    }
+
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_go_imports() {
+        let content = r#"package main
+
+// import "commented/out"
+import "fmt"
+
+import (
+    "github.com/acme/app/internal/store"
+    cfg "github.com/acme/app/config"
+    _ "github.com/lib/pq" /* driver */
+)
+
+func main() { fmt.Println("import \"not/real\"") }
+"#;
+
+        let imports = extract_imports(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("fmt", 4),
+                ("github.com/acme/app/internal/store", 7),
+                ("github.com/acme/app/config", 8),
+                ("github.com/lib/pq", 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_go_mod() {
+        let content = "module github.com/acme/app\n\ngo 1.22\n\nreplace github.com/acme/shared => ../shared\n";
+
+        let (imports, packages) = parse_go_mod(content);
+
+        assert_eq!(packages, vec!["github.com/acme/app"]);
+        assert_eq!(imports.len(), 1);
+        assert_eq!((imports[0].source.as_str(), imports[0].line), ("../shared", 5));
+        assert_eq!(imports[0].kind, ImportKind::Project);
+    }
+}
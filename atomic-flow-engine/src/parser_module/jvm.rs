@@ -0,0 +1,112 @@
+//! Java/Kotlin import extraction
+//! Reads `package` and `import` statements from `.java` and `.kt` files
+
+use super::{blank_comments, ImportInfo, ImportKind, C_FAMILY};
+
+/// Extract imports and the declared package from Java or Kotlin source
+///
+/// Imports carry the fully qualified name they refer to: a class (`a.b.C`),
+/// a static member (`a.b.C.member`) or, for wildcards, the package or class
+/// whose members are imported (`a.b.*` => `a.b` with `is_namespace` set).
+pub fn parse_source(content: &str) -> (Vec<ImportInfo>, Vec<String>) {
+    let code = blank_comments(content, &C_FAMILY);
+    let mut imports = Vec::new();
+    let mut packages = Vec::new();
+
+    for (idx, line) in code.lines().enumerate() {
+        // Java ends statements with `;`, Kotlin usually with the line break
+        for statement in line.split(';').map(str::trim) {
+            if let Some(name) = statement.strip_prefix("package ") {
+                let name = qualified_name(name);
+                if !name.is_empty() {
+                    packages.push(name);
+                }
+            } else if let Some(rest) = statement.strip_prefix("import ") {
+                let rest = rest.trim_start();
+                let rest = rest.strip_prefix("static ").unwrap_or(rest);
+                // Kotlin aliases: `import a.b.C as D`
+                let rest = rest.split(" as ").next().unwrap_or(rest);
+
+                let name = qualified_name(rest);
+                let (source, is_namespace) = match name.strip_suffix(".*") {
+                    Some(prefix) => (prefix.to_string(), true),
+                    None => (name, false),
+                };
+                if source.is_empty() || source.ends_with('*') {
+                    continue;
+                }
+
+                imports.push(ImportInfo {
+                    source,
+                    is_default: false,
+                    is_namespace,
+                    line: idx + 1,
+                    kind: ImportKind::Static,
+                    specifiers: Vec::new(),
+                });
+            }
+        }
+    }
+
+    (imports, packages)
+}
+
+/// Dotted name with whitespace and Kotlin backticks removed, or empty if malformed
+fn qualified_name(text: &str) -> String {
+    let name: String = text.chars().filter(|c| !c.is_whitespace() && *c != '`').collect();
+    let valid = name
+        .split('.')
+        .all(|part| part == "*" || (!part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')));
+
+    if valid {
+        name
+    } else {
+        String::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_java_source() {
+        let content = r#"package com.acme.billing;
+
+import java.util.List;
+import com.acme.shared.*;
+import static com.acme.shared.Guard.requireNonNull;
+import static com.acme.shared.Strings.*;
+// import com.acme.commented.Out;
+
+public class InvoiceService {
+    String s = "import com.acme.Fake;";
+}
+"#;
+
+        let (imports, packages) = parse_source(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.is_namespace, i.line)).collect();
+
+        assert_eq!(packages, vec!["com.acme.billing"]);
+        assert_eq!(
+            found,
+            vec![
+                ("java.util.List", false, 3),
+                ("com.acme.shared", true, 4),
+                ("com.acme.shared.Guard.requireNonNull", false, 5),
+                ("com.acme.shared.Strings", true, 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_kotlin_source() {
+        let content = "package com.acme.web\n\nimport com.acme.billing.InvoiceService as Invoices\nimport com.acme.shared.`fun`.helpers\n";
+
+        let (imports, packages) = parse_source(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+
+        assert_eq!(packages, vec!["com.acme.web"]);
+        assert_eq!(found, vec![("com.acme.billing.InvoiceService", 3), ("com.acme.shared.fun.helpers", 4)]);
+    }
+}
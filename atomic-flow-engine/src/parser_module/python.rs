@@ -0,0 +1,222 @@
+//! Python import extraction
+//! Splits source into logical statements (joining bracketed and backslash
+//! continuations, skipping strings and comments) and reads `import`/`from` statements
+
+use super::{ImportInfo, ImportKind};
+
+/// A logical Python statement with the line it starts on
+struct Statement {
+    text: String,
+    line: usize,
+}
+
+/// Extract imports from Python source
+///
+/// `from pkg import a, b` yields one import per name (`pkg.a`, `pkg.b`) because each
+/// name may be a submodule; the resolver falls back to `pkg` when it is not.
+pub fn extract_imports(content: &str) -> Vec<ImportInfo> {
+    let mut imports = Vec::new();
+
+    for statement in logical_statements(content) {
+        let text = statement.text.trim();
+
+        if let Some(rest) = text.strip_prefix("import ") {
+            // import a.b, c as d
+            for part in rest.split(',') {
+                let module = part.split_whitespace().next().unwrap_or("");
+                if is_module_path(module) {
+                    imports.push(python_import(module.to_string(), false, statement.line));
+                }
+            }
+        } else if let Some(rest) = text.strip_prefix("from ") {
+            // from .x import y, z as w
+            let Some((module, names)) = rest.split_once(" import ") else {
+                continue;
+            };
+            let module = module.trim();
+            if !is_module_path(module) {
+                continue;
+            }
+
+            let names = names.trim().trim_start_matches('(').trim_end_matches(')');
+            for name in names.split(',') {
+                let name = name.split_whitespace().next().unwrap_or("");
+                if name == "*" {
+                    imports.push(python_import(module.to_string(), true, statement.line));
+                } else if !name.is_empty() {
+                    imports.push(python_import(join_module(module, name), false, statement.line));
+                }
+            }
+        }
+    }
+
+    imports
+}
+
+fn python_import(source: String, is_namespace: bool, line: usize) -> ImportInfo {
+    ImportInfo {
+        source,
+        is_default: false,
+        is_namespace,
+        line,
+        kind: ImportKind::Static,
+        specifiers: Vec::new(),
+    }
+}
+
+/// Join a `from` module and an imported name (`..` + `z` => `..z`, `a` + `b` => `a.b`)
+fn join_module(module: &str, name: &str) -> String {
+    if module.ends_with('.') {
+        format!("{}{}", module, name)
+    } else {
+        format!("{}.{}", module, name)
+    }
+}
+
+fn is_module_path(module: &str) -> bool {
+    !module.is_empty()
+        && module.chars().all(|c| c == '.' || c == '_' || c.is_alphanumeric())
+}
+
+/// Split source into logical statements, blanking out strings and comments
+fn logical_statements(content: &str) -> Vec<Statement> {
+    let mut statements = Vec::new();
+    let mut current = String::new();
+    let mut start_line = 1;
+    let mut line = 1;
+    let mut depth = 0usize;
+    let mut chars = content.chars().peekable();
+
+    let mut flush = |current: &mut String, start_line: usize| {
+        if !current.trim().is_empty() {
+            statements.push(Statement {
+                text: std::mem::take(current),
+                line: start_line,
+            });
+        }
+        current.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '#' => {
+                while chars.peek().is_some_and(|&n| n != '\n') {
+                    chars.next();
+                }
+            }
+            '"' | '\'' => {
+                // Triple-quoted strings may span lines
+                let triple = chars.peek() == Some(&c) && {
+                    let mut ahead = chars.clone();
+                    ahead.next();
+                    ahead.peek() == Some(&c)
+                };
+                if triple {
+                    chars.next();
+                    chars.next();
+                }
+
+                let mut run = 0;
+                while let Some(s) = chars.next() {
+                    match s {
+                        '\\' => {
+                            if chars.next() == Some('\n') {
+                                line += 1;
+                            }
+                            run = 0;
+                        }
+                        '\n' => {
+                            line += 1;
+                            if !triple {
+                                break;
+                            }
+                            run = 0;
+                        }
+                        s if s == c => {
+                            run += 1;
+                            if !triple || run == 3 {
+                                break;
+                            }
+                        }
+                        _ => run = 0,
+                    }
+                }
+                current.push_str("\"\"");
+            }
+            '(' | '[' | '{' => {
+                depth += 1;
+                current.push(c);
+            }
+            ')' | ']' | '}' => {
+                depth = depth.saturating_sub(1);
+                current.push(c);
+            }
+            '\\' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                line += 1;
+                current.push(' ');
+            }
+            '\n' => {
+                line += 1;
+                if depth == 0 {
+                    flush(&mut current, start_line);
+                    start_line = line;
+                } else {
+                    current.push(' ');
+                }
+            }
+            ';' if depth == 0 => {
+                flush(&mut current, start_line);
+                start_line = line;
+            }
+            _ => {
+                if current.trim().is_empty() {
+                    start_line = line;
+                }
+                current.push(c);
+            }
+        }
+    }
+    flush(&mut current, start_line);
+
+    statements
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_python_imports() {
+        let content = r#"
+import os, app.models as models
+from .services import (
+    auth,
+    billing as bill,
+)
+from .. import config  # trailing comment
+from .utils import *
+"""
+import not_an_import
+"""
+x = 1; import json
+"#;
+
+        let imports = extract_imports(content);
+        let sources: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+
+        assert_eq!(
+            sources,
+            vec![
+                ("os", 2),
+                ("app.models", 2),
+                (".services.auth", 3),
+                (".services.billing", 3),
+                ("..config", 7),
+                (".utils", 8),
+                ("json", 12),
+            ]
+        );
+        assert!(imports.iter().any(|i| i.source == ".utils" && i.is_namespace));
+    }
+}
@@ -0,0 +1,596 @@
+//! Rust module and `use` extraction
+//! A small lexer skips comments, strings and char literals; items are then read
+//! token by token so `mod` declarations (with `#[path]`) and `use` trees are exact
+
+use super::{ImportInfo, ImportKind};
+
+/// Dependency tables of a Cargo manifest
+const DEPENDENCY_TABLES: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Punct(char),
+}
+
+/// Extract `mod foo;`, `use` trees and `extern crate` from Rust source
+///
+/// `mod` imports carry the module name, or the `#[path]` value when present.
+/// `use` imports carry the full path (`crate::a::b`), already adjusted for any
+/// inline `mod x { ... }` blocks they appear in.
+pub fn extract_imports(content: &str) -> Vec<ImportInfo> {
+    let tokens = tokenize(content);
+    let mut imports = Vec::new();
+    let mut pending_path: Option<String> = None;
+    // Brace depth at which each enclosing inline module was opened
+    let mut inline_mods: Vec<(String, usize)> = Vec::new();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i < tokens.len() {
+        let (token, line) = &tokens[i];
+        match token {
+            Token::Punct('#') => {
+                // #[path = "x.rs"] (inner `#![...]` attributes are skipped too)
+                let mut j = i + 1;
+                if tokens.get(j).map(|t| &t.0) == Some(&Token::Punct('!')) {
+                    j += 1;
+                }
+                if tokens.get(j).map(|t| &t.0) == Some(&Token::Punct('[')) {
+                    let end = matching_bracket(&tokens, j);
+                    if let Some([(Token::Ident(name), _), (Token::Punct('='), _), (Token::Str(value), _)]) =
+                        tokens.get(j + 1..end)
+                    {
+                        if name == "path" {
+                            pending_path = Some(value.clone());
+                        }
+                    }
+                    i = end + 1;
+                    continue;
+                }
+            }
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => {
+                depth = depth.saturating_sub(1);
+                if inline_mods.last().is_some_and(|(_, d)| *d == depth) {
+                    inline_mods.pop();
+                }
+                pending_path = None;
+            }
+            Token::Punct(';') => pending_path = None,
+            Token::Ident(kw) if kw == "mod" => {
+                if let Some((Token::Ident(name), _)) = tokens.get(i + 1) {
+                    match tokens.get(i + 2).map(|t| &t.0) {
+                        Some(Token::Punct(';')) => {
+                            let source = match pending_path.take() {
+                                Some(path) => path,
+                                None => inline_mods
+                                    .iter()
+                                    .map(|(m, _)| m.as_str())
+                                    .chain(std::iter::once(name.as_str()))
+                                    .collect::<Vec<_>>()
+                                    .join("/"),
+                            };
+                            imports.push(rust_import(source, *line, ImportKind::Module));
+                            i += 3;
+                            continue;
+                        }
+                        Some(Token::Punct('{')) => {
+                            inline_mods.push((name.clone(), depth));
+                            pending_path = None;
+                            depth += 1;
+                            i += 3;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Token::Ident(kw) if kw == "extern" => {
+                if let [(Token::Ident(krate), _), (Token::Ident(name), _), ..] = &tokens[i + 1..] {
+                    if krate == "crate" {
+                        imports.push(rust_import(name.clone(), *line, ImportKind::Static));
+                    }
+                }
+            }
+            Token::Ident(kw) if kw == "use" => {
+                let end = tokens[i..]
+                    .iter()
+                    .position(|(t, _)| *t == Token::Punct(';'))
+                    .map(|p| i + p)
+                    .unwrap_or(tokens.len());
+                let tree: Vec<&Token> = tokens[i + 1..end].iter().map(|(t, _)| t).collect();
+
+                for path in expand_use_tree(&tree) {
+                    let path = adjust_for_inline_modules(&path, inline_mods.len());
+                    imports.push(rust_import(path, *line, ImportKind::Static));
+                }
+                i = end + 1;
+                pending_path = None;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    imports
+}
+
+/// Read a `Cargo.toml`: the package name it declares, plus workspace members
+/// and `path` dependencies as project-level imports
+///
+/// Package names are normalized to their crate form (`my-crate` => `my_crate`).
+pub fn parse_manifest(content: &str) -> (Vec<ImportInfo>, Vec<String>) {
+    let mut imports = Vec::new();
+    let mut packages = Vec::new();
+
+    let Ok(manifest) = toml::from_str::<toml::Table>(content) else {
+        return (imports, packages);
+    };
+    let lines = super::LineIndex::new(content);
+    let line_of = |value: &str| {
+        content
+            .find(&format!("\"{}\"", value))
+            .map(|offset| lines.line_of(offset as u32))
+            .unwrap_or(1)
+    };
+
+    if let Some(name) = manifest
+        .get("package")
+        .and_then(|p| p.get("name"))
+        .and_then(|n| n.as_str())
+    {
+        packages.push(name.replace('-', "_"));
+    }
+
+    let workspace = manifest.get("workspace");
+    for member in workspace
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|m| m.as_str())
+    {
+        imports.push(rust_import(member.to_string(), line_of(member), ImportKind::Project));
+    }
+
+    for dependency in dependency_tables(&manifest).into_iter().flat_map(|t| t.values()) {
+        if let Some(path) = dependency.get("path").and_then(|p| p.as_str()) {
+            imports.push(rust_import(path.to_string(), line_of(path), ImportKind::Project));
+        }
+    }
+
+    (imports, packages)
+}
+
+/// Crates a `Cargo.toml` takes from a registry or git, in their `use` form (`serde-json` => `serde_json`)
+///
+/// `path` dependencies are left out: they are project crates.
+pub fn manifest_dependencies(content: &str) -> Vec<String> {
+    let Ok(manifest) = toml::from_str::<toml::Table>(content) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = dependency_tables(&manifest)
+        .into_iter()
+        .flat_map(|t| t.iter())
+        .filter(|(_, dependency)| dependency.get("path").is_none())
+        .map(|(name, _)| name.replace('-', "_"))
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// `[dependencies]`, `[workspace.dependencies]` and `[target.'cfg(..)'.dependencies]` tables
+fn dependency_tables(manifest: &toml::Table) -> Vec<&toml::Table> {
+    let workspace = manifest.get("workspace").and_then(|w| w.as_table());
+    let targets = manifest
+        .get("target")
+        .and_then(|t| t.as_table())
+        .into_iter()
+        .flat_map(|targets| targets.values().filter_map(|t| t.as_table()));
+
+    [Some(manifest), workspace]
+        .into_iter()
+        .flatten()
+        .chain(targets)
+        .flat_map(|parent| {
+            DEPENDENCY_TABLES
+                .iter()
+                .filter_map(|name| parent.get(*name).and_then(|t| t.as_table()))
+        })
+        .collect()
+}
+
+fn rust_import(source: String, line: usize, kind: ImportKind) -> ImportInfo {
+    ImportInfo {
+        source,
+        is_default: false,
+        is_namespace: false,
+        line,
+        kind,
+        specifiers: Vec::new(),
+    }
+}
+
+/// Re-anchor `self::`/`super::` paths written inside inline modules to the file's module
+///
+/// A path that stays inside the inline modules refers to the file itself and becomes `self`.
+fn adjust_for_inline_modules(path: &str, inline_depth: usize) -> String {
+    if inline_depth == 0 {
+        return path.to_string();
+    }
+
+    let segments: Vec<&str> = path.split("::").collect();
+    let supers = segments.iter().take_while(|s| **s == "super").count();
+    if segments[0] != "self" && supers == 0 {
+        return path.to_string();
+    }
+    if supers < inline_depth {
+        return "self".to_string();
+    }
+
+    let mut adjusted: Vec<&str> = vec!["super"; supers - inline_depth];
+    if adjusted.is_empty() {
+        adjusted.push("self");
+    }
+    adjusted.extend(&segments[supers..]);
+    adjusted.join("::")
+}
+
+/// Index of the `]` closing the `[` at `open`
+fn matching_bracket(tokens: &[(Token, usize)], open: usize) -> usize {
+    let mut depth = 0;
+    for (idx, (token, _)) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Punct('[') => depth += 1,
+            Token::Punct(']') => {
+                depth -= 1;
+                if depth == 0 {
+                    return idx;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len() - 1
+}
+
+/// Expand a use tree into flat paths, dropping aliases and globs
+///
+/// `crate::a::{b, c::d as e, self}` => `crate::a::b`, `crate::a::c::d`, `crate::a`
+fn expand_use_tree(tokens: &[&Token]) -> Vec<String> {
+    let mut paths = Vec::new();
+    expand_into(tokens, &mut Vec::new(), &mut paths);
+    paths
+}
+
+fn expand_into(tokens: &[&Token], prefix: &mut Vec<String>, out: &mut Vec<String>) {
+    let base_len = prefix.len();
+    let mut i = 0;
+
+    while i < tokens.len() {
+        match tokens[i] {
+            Token::Ident(name) if name == "as" => {
+                // Skip the alias
+                i += 2;
+                continue;
+            }
+            Token::Ident(name) if name == "self" && !prefix.is_empty() && prefix.len() == base_len => {}
+            Token::Ident(name) => prefix.push(name.clone()),
+            Token::Punct('{') => {
+                let close = matching_brace(tokens, i);
+                for item in split_top_level(&tokens[i + 1..close]) {
+                    expand_into(item, prefix, out);
+                }
+                prefix.truncate(base_len);
+                return;
+            }
+            Token::Punct(',') => {
+                push_path(prefix, out);
+                prefix.truncate(base_len);
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    push_path(prefix, out);
+    prefix.truncate(base_len);
+}
+
+fn push_path(prefix: &[String], out: &mut Vec<String>) {
+    if !prefix.is_empty() {
+        out.push(prefix.join("::"));
+    }
+}
+
+fn matching_brace(tokens: &[&Token], open: usize) -> usize {
+    let mut depth = 0;
+    for (idx, token) in tokens.iter().enumerate().skip(open) {
+        match token {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return idx;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+fn split_top_level<'t>(tokens: &'t [&'t Token]) -> Vec<&'t [&'t Token]> {
+    let mut items = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (idx, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Punct('{') => depth += 1,
+            Token::Punct('}') => depth -= 1,
+            Token::Punct(',') if depth == 0 => {
+                items.push(&tokens[start..idx]);
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    items.push(&tokens[start..]);
+    items
+}
+
+/// Tokenize Rust source into identifiers, string literals and punctuation
+///
+/// `::` is dropped as a separator token so paths read as consecutive idents
+/// joined by the expansion step; comments, char literals and lifetimes are skipped.
+fn tokenize(content: &str) -> Vec<(Token, usize)> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = content.chars().collect();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '\n' => {
+                line += 1;
+                i += 1;
+            }
+            c if c.is_whitespace() => i += 1,
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                // Block comments nest in Rust
+                let mut depth = 0;
+                while i < chars.len() {
+                    if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+                        depth += 1;
+                        i += 2;
+                    } else if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                        depth -= 1;
+                        i += 2;
+                        if depth == 0 {
+                            break;
+                        }
+                    } else {
+                        if chars[i] == '\n' {
+                            line += 1;
+                        }
+                        i += 1;
+                    }
+                }
+            }
+            '"' => {
+                let (value, next, newlines) = read_string(&chars, i + 1, 0, false);
+                tokens.push((Token::Str(value), line));
+                line += newlines;
+                i = next;
+            }
+            'r' | 'b' if is_prefixed_string_start(&chars, i) => {
+                // b"..", r".." / r#".."#, br".."
+                let mut j = i + 1;
+                let raw = chars[i] == 'r' || chars[j] == 'r';
+                if chars[i] == 'b' && raw {
+                    j += 1;
+                }
+                let mut hashes = 0;
+                while chars.get(j) == Some(&'#') {
+                    hashes += 1;
+                    j += 1;
+                }
+                // `j` is at the opening quote
+                let (value, next, newlines) = read_string(&chars, j + 1, hashes, raw);
+                tokens.push((Token::Str(value), line));
+                line += newlines;
+                i = next;
+            }
+            '\'' => {
+                // Char literal ('a', '\n') or lifetime ('a)
+                if chars.get(i + 1) == Some(&'\\') {
+                    i += 2;
+                    while i < chars.len() && chars[i] != '\'' {
+                        i += 1;
+                    }
+                    i += 1;
+                } else if chars.get(i + 2) == Some(&'\'') {
+                    i += 3;
+                } else {
+                    i += 1;
+                    while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                        i += 1;
+                    }
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                // Raw identifiers: r#type
+                if i == start + 1 && c == 'r' && chars.get(i) == Some(&'#') {
+                    i += 1;
+                    continue;
+                }
+                tokens.push((Token::Ident(chars[start..i].iter().collect()), line));
+            }
+            c if c.is_numeric() => {
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+            }
+            ':' if chars.get(i + 1) == Some(&':') => i += 2,
+            _ => {
+                tokens.push((Token::Punct(c), line));
+                i += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Whether a byte or raw string literal starts at `i`
+fn is_prefixed_string_start(chars: &[char], i: usize) -> bool {
+    let mut j = i + 1;
+    if chars[i] == 'b' {
+        if chars.get(j) == Some(&'"') {
+            return true;
+        }
+        if chars.get(j) != Some(&'r') {
+            return false;
+        }
+        j += 1;
+    }
+    while chars.get(j) == Some(&'#') {
+        j += 1;
+    }
+    chars.get(j) == Some(&'"') && (j > i + 1 || chars[i] == 'r')
+}
+
+/// Read a string body starting after the opening quote; raw strings have no escapes
+/// Returns the value, the index after the closing delimiter and the newlines consumed
+fn read_string(chars: &[char], start: usize, hashes: usize, raw: bool) -> (String, usize, usize) {
+    let mut value = String::new();
+    let mut newlines = 0;
+    let mut i = start;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '\\' && !raw {
+            if let Some(&escaped) = chars.get(i + 1) {
+                if escaped == '\n' {
+                    newlines += 1;
+                }
+                value.push(escaped);
+            }
+            i += 2;
+            continue;
+        }
+        if c == '"' && chars[i + 1..].iter().take(hashes).filter(|h| **h == '#').count() == hashes {
+            return (value, i + 1 + hashes, newlines);
+        }
+        if c == '\n' {
+            newlines += 1;
+        }
+        value.push(c);
+        i += 1;
+    }
+
+    (value, i, newlines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_manifest() {
+        let content = r#"
+[package]
+name = "atomic-flow-engine"
+
+[workspace]
+members = ["crates/*"]
+
+[dependencies]
+serde = "1.0"
+flow-core = { path = "crates/core" }
+
+[target.'cfg(unix)'.dev-dependencies]
+flow-test = { path = "../flow-test" }
+"#;
+
+        let (imports, packages) = parse_manifest(content);
+        let sources: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+
+        assert_eq!(packages, vec!["atomic_flow_engine"]);
+        assert_eq!(sources, vec![("crates/*", 6), ("crates/core", 10), ("../flow-test", 13)]);
+        assert_eq!(manifest_dependencies(content), vec!["serde"]);
+        assert!(imports.iter().all(|i| i.kind == ImportKind::Project));
+    }
+
+    #[test]
+    fn test_extract_rust_imports() {
+        let content = r##"
+//! mod not_a_module;
+mod parser;
+#[path = "generated/bindings.rs"]
+mod bindings;
+pub(crate) mod layout { mod nested; }
+
+use crate::parser::{ImportInfo, ParseResult as PR, self};
+use super::sibling::*;
+extern crate serde;
+
+fn f<'a>(x: &'a str) -> char { let _s = r#"use fake::path;"#; 'x' }
+
+#[cfg(test)]
+mod tests {
+    use super::helper;
+    use super::super::outer;
+}
+"##;
+
+        let imports = extract_imports(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.kind, i.line)).collect();
+
+        assert_eq!(
+            found,
+            vec![
+                ("parser", ImportKind::Module, 3),
+                ("generated/bindings.rs", ImportKind::Module, 5),
+                ("layout/nested", ImportKind::Module, 6),
+                ("crate::parser::ImportInfo", ImportKind::Static, 8),
+                ("crate::parser::ParseResult", ImportKind::Static, 8),
+                ("crate::parser", ImportKind::Static, 8),
+                ("super::sibling", ImportKind::Static, 9),
+                ("serde", ImportKind::Static, 10),
+                ("self::helper", ImportKind::Static, 16),
+                ("super::outer", ImportKind::Static, 17),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_after_byte_and_raw_strings() {
+        let content = r##"
+const EMPTY: &[u8] = b"";
+const QUOTE: &[u8] = b"\"";
+const DIR: &str = r"C:\";
+mod after_strings;
+use crate::after::Item;
+"##;
+
+        let imports = extract_imports(content);
+        let found: Vec<_> = imports.iter().map(|i| (i.source.as_str(), i.line)).collect();
+        assert_eq!(found, vec![("after_strings", 5), ("crate::after::Item", 6)]);
+    }
+}
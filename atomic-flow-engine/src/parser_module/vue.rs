@@ -0,0 +1,310 @@
+//! Vue single-file component support
+//! Pulls `<script>`/`<script setup>` and `<style>` blocks out of an SFC so only
+//! real code is parsed, with line numbers mapped back to the `.vue` file
+
+use oxc_span::SourceType;
+
+use super::{blank_comments, parse_ecmascript, CommentSyntax, ExportInfo, ImportInfo, ImportKind, LineIndex};
+
+/// Stylesheet comments, including SCSS/Less `//` lines; quoted strings are kept
+const CSS: CommentSyntax = CommentSyntax {
+    quotes: &['"', '\''],
+    raw_quote: None,
+    verbatim: false,
+    blank_strings: false,
+    url_slashes: true,
+};
+
+/// A top-level block of a single-file component
+struct SfcBlock<'s> {
+    /// Raw attribute text of the opening tag (e.g. ` setup lang="ts"`)
+    attrs: &'s str,
+    /// Block body between the opening and closing tags
+    content: &'s str,
+    /// Byte offset of `content` within the SFC
+    offset: usize,
+}
+
+/// Parse the script and style blocks of a Vue SFC
+pub fn parse_sfc(content: &str) -> (Vec<ImportInfo>, Vec<ExportInfo>) {
+    let lines = LineIndex::new(content);
+    let mut imports = Vec::new();
+    let mut exports = Vec::new();
+
+    for block in top_level_blocks(content, "script") {
+        let first_line = lines.line_of(block.offset as u32);
+
+        // <script src="./logic.ts"></script>
+        if let Some(src) = attr_value(block.attrs, "src") {
+            imports.push(ImportInfo {
+                source: src.to_string(),
+                is_default: false,
+                is_namespace: false,
+                line: first_line,
+                kind: ImportKind::Static,
+                specifiers: Vec::new(),
+            });
+        }
+
+        let source_type = match attr_value(block.attrs, "lang") {
+            Some("ts") => SourceType::ts(),
+            Some("tsx") => SourceType::tsx(),
+            _ => SourceType::jsx(),
+        };
+
+        let (block_imports, block_exports) = parse_ecmascript(block.content, source_type);
+        imports.extend(block_imports.into_iter().map(|mut imp| {
+            imp.line += first_line - 1;
+            imp
+        }));
+        exports.extend(block_exports);
+    }
+
+    // The compiled component is always the module's default export
+    if !exports.iter().any(|e| e.is_default) {
+        exports.push(ExportInfo {
+            name: "default".to_string(),
+            is_default: true,
+            source: None,
+            imported: None,
+        });
+    }
+
+    for block in top_level_blocks(content, "style") {
+        let first_line = lines.line_of(block.offset as u32);
+        imports.extend(extract_style_imports(block.content).into_iter().map(|(source, line)| {
+            ImportInfo {
+                source,
+                is_default: false,
+                is_namespace: false,
+                line: line + first_line - 1,
+                kind: ImportKind::Style,
+                specifiers: Vec::new(),
+            }
+        }));
+    }
+
+    (imports, exports)
+}
+
+/// Find top-level `<tag>` blocks, skipping comments and the `<template>` tree
+fn top_level_blocks<'s>(content: &'s str, tag: &str) -> Vec<SfcBlock<'s>> {
+    let mut blocks = Vec::new();
+    let mut pos = 0;
+
+    while let Some(rel) = content[pos..].find('<') {
+        let start = pos + rel;
+        let rest = &content[start..];
+
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map(|end| start + end + 3).unwrap_or(content.len());
+            continue;
+        }
+
+        let Some(name) = opening_tag_name(rest) else {
+            pos = start + 1;
+            continue;
+        };
+        let Some(open_end) = rest.find('>').map(|end| start + end + 1) else {
+            break;
+        };
+
+        let close_tag = format!("</{}", name);
+        let block_end = if name == "template" {
+            find_template_close(content, open_end)
+        } else {
+            content[open_end..].find(&close_tag).map(|end| open_end + end)
+        };
+        let Some(block_end) = block_end else {
+            break;
+        };
+
+        if name == tag {
+            blocks.push(SfcBlock {
+                attrs: &content[start + 1 + name.len()..open_end - 1],
+                content: &content[open_end..block_end],
+                offset: open_end,
+            });
+        }
+
+        pos = content[block_end..]
+            .find('>')
+            .map(|end| block_end + end + 1)
+            .unwrap_or(content.len());
+    }
+
+    blocks
+}
+
+/// Name of the SFC block opened at the start of `text`, if any
+fn opening_tag_name(text: &str) -> Option<&'static str> {
+    ["template", "script", "style"].into_iter().find(|name| {
+        text[1..].starts_with(name)
+            && text[1 + name.len()..]
+                .chars()
+                .next()
+                .is_some_and(|c| c == '>' || c.is_whitespace())
+    })
+}
+
+/// Find the `</template>` matching an opening tag, accounting for nested templates
+fn find_template_close(content: &str, from: usize) -> Option<usize> {
+    let mut depth = 1;
+    let mut pos = from;
+
+    while let Some(rel) = content[pos..].find("template") {
+        let at = pos + rel;
+        if content[..at].ends_with("</") {
+            depth -= 1;
+            if depth == 0 {
+                return Some(at - 2);
+            }
+        } else if content[..at].ends_with('<') {
+            depth += 1;
+        }
+        pos = at + "template".len();
+    }
+
+    None
+}
+
+/// Read an attribute value from raw tag attribute text
+fn attr_value<'s>(attrs: &'s str, name: &str) -> Option<&'s str> {
+    let mut rest = attrs;
+    while let Some(idx) = rest.find(name) {
+        let before_ok = rest[..idx].chars().last().is_none_or(|c| c.is_whitespace());
+        let after = rest[idx + name.len()..].trim_start();
+        if before_ok {
+            if let Some(value) = after.strip_prefix('=') {
+                let value = value.trim_start();
+                let quote = value.chars().next()?;
+                if quote == '"' || quote == '\'' {
+                    let body = &value[1..];
+                    return body.find(quote).map(|end| &body[..end]);
+                }
+            }
+        }
+        rest = &rest[idx + name.len()..];
+    }
+    None
+}
+
+/// Extract `@import`/`@use`/`@forward` targets from CSS, SCSS or Less source
+/// Returns the target and its 1-based line within the block
+pub fn extract_style_imports(css: &str) -> Vec<(String, usize)> {
+    let mut imports = Vec::new();
+    let lines = LineIndex::new(css);
+    let stripped = blank_comments(css, &CSS);
+
+    for at_rule in ["@import", "@use", "@forward"] {
+        let mut pos = 0;
+        while let Some(rel) = stripped[pos..].find(at_rule) {
+            let start = pos + rel;
+            pos = start + at_rule.len();
+
+            let rest = &stripped[pos..];
+            if !rest.starts_with(|c: char| c.is_whitespace()) {
+                continue;
+            }
+            let statement = &rest[..rest.find(';').unwrap_or(rest.len())];
+
+            for target in quoted_targets(statement) {
+                if target.contains("://") || target.starts_with("//") {
+                    continue;
+                }
+                imports.push((target, lines.line_of(start as u32)));
+            }
+        }
+    }
+
+    imports.sort_by_key(|(_, line)| *line);
+    imports
+}
+
+/// Targets of an at-rule, e.g. `"a", "b"` or `url(./c.css) screen`
+fn quoted_targets(statement: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut rest = statement;
+
+    loop {
+        let trimmed = rest.trim_start();
+        let (target, remaining) = if let Some(url) = trimmed.strip_prefix("url(") {
+            let Some(end) = url.find(')') else { break };
+            (url[..end].trim().trim_matches(|c| c == '"' || c == '\''), &url[end + 1..])
+        } else if let Some(quote) = trimmed.chars().next().filter(|c| *c == '"' || *c == '\'') {
+            let body = &trimmed[1..];
+            let Some(end) = body.find(quote) else { break };
+            (&body[..end], &body[end + 1..])
+        } else {
+            break;
+        };
+
+        if !target.is_empty() {
+            targets.push(target.to_string());
+        }
+
+        match remaining.trim_start().strip_prefix(',') {
+            Some(next) => rest = next,
+            None => break,
+        }
+    }
+
+    targets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SFC: &str = r#"<template>
+  <div>
+    <template v-if="ok"><span>import Fake from './fake'</span></template>
+  </div>
+</template>
+
+<script lang="ts">
+import { defineComponent } from 'vue'
+export default defineComponent({})
+</script>
+
+<script setup lang="ts">
+import Child from './Child.vue'
+import {
+  useAuth,
+} from '@/composables/auth'
+</script>
+
+<style scoped lang="scss">
+/* @import './ignored.scss'; */
+@use './tokens';
+@import url("./base.css"), 'https://fonts.example.com/x.css';
+</style>
+"#;
+
+    #[test]
+    fn test_parse_sfc_blocks() {
+        let (imports, exports) = parse_sfc(SFC);
+
+        let scripts: Vec<_> = imports.iter().filter(|i| i.kind == ImportKind::Static).collect();
+        assert_eq!(scripts.len(), 3);
+        assert_eq!((scripts[0].source.as_str(), scripts[0].line), ("vue", 8));
+        assert_eq!((scripts[1].source.as_str(), scripts[1].line), ("./Child.vue", 13));
+        assert_eq!((scripts[2].source.as_str(), scripts[2].line), ("@/composables/auth", 14));
+
+        let styles: Vec<_> = imports.iter().filter(|i| i.kind == ImportKind::Style).collect();
+        assert_eq!(styles.len(), 2);
+        assert_eq!((styles[0].source.as_str(), styles[0].line), ("./tokens", 21));
+        assert_eq!((styles[1].source.as_str(), styles[1].line), ("./base.css", 22));
+
+        assert_eq!(exports.iter().filter(|e| e.is_default).count(), 1);
+    }
+
+    #[test]
+    fn test_script_setup_only_has_default_export() {
+        let (imports, exports) = parse_sfc("<script setup>\nimport a from './a'\n</script>\n");
+
+        assert_eq!(imports.len(), 1);
+        assert_eq!(imports[0].line, 2);
+        assert!(exports.iter().any(|e| e.name == "default" && e.is_default));
+    }
+}
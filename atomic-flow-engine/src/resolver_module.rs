@@ -0,0 +1,1281 @@
+//! Resolver module for mapping import specifiers to project files
+//! Each language has its own resolution rules; all of them end in node ids
+
+use std::collections::{HashMap, HashSet};
+
+use crate::config_module::ProjectConfig;
+use crate::external_module::{ExternalPackage, Registry, NODE_BUILTINS, PYTHON_STDLIB};
+use crate::package_module::split_package_specifier;
+use crate::parser_module::{ImportInfo, ImportKind, ParseResult};
+
+/// Manifest files whose project-level references are resolved to other manifests
+const MANIFEST_FILES: [&str; 2] = ["Cargo.toml", "go.mod"];
+
+/// Crate roots that are always part of the Rust standard distribution
+const RUST_STD_CRATES: [&str; 5] = ["std", "core", "alloc", "proc_macro", "test"];
+
+/// Resolves imports against the set of scanned nodes
+pub struct Resolver<'a> {
+    node_ids: &'a HashSet<String>,
+    /// Ids of the directories among `node_ids`
+    dirs: HashSet<String>,
+    /// Extra roots (relative to the project) searched for absolute imports
+    source_roots: Vec<String>,
+    /// Rust crate name => crate directory (`""` for the project root)
+    crates: HashMap<String, String>,
+    /// Directory of each `Cargo.toml` => crates it depends on
+    cargo_dependencies: HashMap<String, HashSet<String>>,
+    /// Go module path => module directory, from `go.mod` files
+    go_modules: Vec<(String, String)>,
+    /// C# namespace => `.cs` files declaring it (sorted)
+    cs_namespaces: HashMap<String, Vec<String>>,
+    /// C# root namespace => `.csproj` file
+    cs_projects: Vec<(String, String)>,
+    /// Java/Kotlin package => files declaring it (sorted)
+    jvm_packages: HashMap<String, Vec<String>>,
+    /// Java/Kotlin source roots (`src/main/java` and the like) followed by the
+    /// configured source roots
+    jvm_roots: Vec<String>,
+    /// Aliases and workspace packages for JavaScript and TypeScript imports
+    config: ProjectConfig,
+}
+
+impl<'a> Resolver<'a> {
+    pub fn new(
+        node_ids: &'a HashSet<String>,
+        parse_results: &HashMap<String, ParseResult>,
+        source_roots: &[String],
+    ) -> Self {
+        // The project root itself is always a source root
+        let mut roots = vec![String::new()];
+        roots.extend(
+            source_roots
+                .iter()
+                .map(|r| r.replace('\\', "/").trim_matches('/').to_string())
+                .filter(|r| !r.is_empty() && r != "."),
+        );
+
+        let mut crates = HashMap::new();
+        let mut cargo_dependencies = HashMap::new();
+        let mut go_modules = Vec::new();
+        let mut cs_namespaces: HashMap<String, Vec<String>> = HashMap::new();
+        let mut cs_projects = Vec::new();
+        let mut jvm_packages: HashMap<String, Vec<String>> = HashMap::new();
+        let mut jvm_roots = Vec::new();
+        for (id, result) in parse_results {
+            if let Some((dir, "Cargo.toml")) = manifest_of(id) {
+                cargo_dependencies.insert(dir.to_string(), result.dependencies.iter().cloned().collect());
+            }
+            for name in &result.packages {
+                if is_jvm_file(id) {
+                    jvm_packages.entry(name.clone()).or_default().push(id.clone());
+                    jvm_roots.extend(jvm_source_root(id, name));
+                    continue;
+                }
+                if id.ends_with(".cs") {
+                    cs_namespaces.entry(name.clone()).or_default().push(id.clone());
+                    continue;
+                }
+                if id.ends_with(".csproj") {
+                    cs_projects.push((name.clone(), id.clone()));
+                    continue;
+                }
+                match manifest_of(id) {
+                    Some((dir, "Cargo.toml")) => {
+                        crates.insert(name.clone(), dir.to_string());
+                    }
+                    Some((dir, "go.mod")) => go_modules.push((name.clone(), dir.to_string())),
+                    _ => {}
+                }
+            }
+        }
+
+        for files in cs_namespaces.values_mut().chain(jvm_packages.values_mut()) {
+            files.sort();
+        }
+        go_modules.sort();
+        cs_projects.sort();
+        jvm_roots.sort();
+        jvm_roots.dedup();
+        for root in &roots {
+            if !jvm_roots.contains(root) {
+                jvm_roots.push(root.clone());
+            }
+        }
+
+        let mut dirs = HashSet::new();
+        for id in node_ids {
+            let mut current = id.as_str();
+            while let Some((parent, _)) = current.rsplit_once('/') {
+                if !dirs.insert(parent.to_string()) {
+                    break;
+                }
+                current = parent;
+            }
+        }
+
+        Self {
+            node_ids,
+            dirs,
+            source_roots: roots,
+            crates,
+            cargo_dependencies,
+            go_modules,
+            cs_namespaces,
+            cs_projects,
+            jvm_packages,
+            jvm_roots,
+            config: ProjectConfig::default(),
+        }
+    }
+
+    /// Resolve JavaScript and TypeScript imports through the project's configuration
+    pub fn with_config(mut self, config: ProjectConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Node that represents `file` as the source of its links
+    ///
+    /// Go depends on packages rather than files, so Go files speak for their package directory.
+    pub fn link_source(&self, file: &str) -> String {
+        let (dir, _) = split_parent(file);
+        if file.ends_with(".go") && self.node_ids.contains(dir) {
+            dir.to_string()
+        } else {
+            file.to_string()
+        }
+    }
+
+    /// Resolve an import made by `from_file` to the node ids it depends on
+    pub fn resolve(&self, from_file: &str, import: &ImportInfo) -> Vec<String> {
+        let ext = from_file.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+
+        if manifest_of(from_file).is_some() {
+            return self.resolve_manifest_reference(from_file, &import.source);
+        }
+
+        match ext {
+            "go" => self.resolve_go_package(&import.source),
+            "cs" => self.resolve_csharp_using(&import.source),
+            "java" | "kt" | "kts" => self.resolve_jvm_import(from_file, import),
+            "csproj" => join_path(split_parent(from_file).0, &import.source)
+                .filter(|target| self.node_ids.contains(target))
+                .into_iter()
+                .collect(),
+            "py" | "pyi" => self.resolve_python(from_file, &import.source).into_iter().collect(),
+            "rs" => match import.kind {
+                ImportKind::Module => self.resolve_rust_mod(from_file, &import.source),
+                _ => self.resolve_rust_path(from_file, &import.source),
+            }
+            .into_iter()
+            .collect(),
+            _ => self.resolve_script(from_file, import).into_iter().collect(),
+        }
+    }
+
+    /// Third-party package an import refers to, when it is not part of the project
+    ///
+    /// Standard libraries (Node.js core modules, the Python stdlib, `std`) are not packages;
+    /// neither are aliased specifiers and workspace packages, which point into the project.
+    pub fn external_package(&self, from_file: &str, import: &ImportInfo) -> Option<ExternalPackage> {
+        let source = import.source.as_str();
+        let ext = from_file.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+        if manifest_of(from_file).is_some() {
+            return None;
+        }
+
+        match ext {
+            "py" | "pyi" => {
+                let top = source.split('.').next()?;
+                (!top.is_empty() && !PYTHON_STDLIB.contains(&top)).then(|| ExternalPackage::new(Registry::Pypi, top))
+            }
+            // Only crates the nearest `Cargo.toml` depends on; other paths are modules the
+            // resolver missed (inline, generated), not crates.io packages
+            "rs" if import.kind == ImportKind::Static => {
+                let first = source.split("::").next()?;
+                let is_local = self.crates.contains_key(first) || RUST_STD_CRATES.contains(&first);
+                (!is_local && self.nearest_cargo_dependencies(from_file)?.contains(first))
+                    .then(|| ExternalPackage::new(Registry::Crates, first))
+            }
+            "go" | "cs" | "java" | "kt" | "kts" | "csproj" | "rs" => None,
+            _ if matches!(import.kind, ImportKind::Static | ImportKind::Dynamic | ImportKind::ReExport) => {
+                // Bundler aliases may redirect to another package (`vue` => `vue/dist/vue.esm.js`)
+                let redirected = self.config.aliases_for(from_file).find_map(|alias| alias.redirect(source));
+                let source = redirected.as_deref().unwrap_or(source);
+                // `node:fs`, `virtual:pwa`, `https://...`
+                if source.contains(':') {
+                    return None;
+                }
+                let (name, _) = split_package_specifier(source)?;
+                let unscoped = match name.strip_prefix('@') {
+                    Some(scoped) => scoped.split_once('/').filter(|(scope, _)| !scope.is_empty())?.1,
+                    None => name,
+                };
+                let is_package_name = unscoped.starts_with(|c: char| c.is_ascii_lowercase() || c.is_ascii_digit())
+                    && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "@/-._".contains(c));
+                (is_package_name && !self.is_project_specifier(from_file, source) && !NODE_BUILTINS.contains(&name))
+                    .then(|| ExternalPackage::new(Registry::Npm, name))
+            }
+            _ => None,
+        }
+    }
+
+    /// Dependencies of the `Cargo.toml` nearest above `file`
+    fn nearest_cargo_dependencies(&self, file: &str) -> Option<&HashSet<String>> {
+        let mut dir = split_parent(file).0;
+        loop {
+            if let Some(dependencies) = self.cargo_dependencies.get(dir) {
+                return Some(dependencies);
+            }
+            if dir.is_empty() {
+                return None;
+            }
+            dir = split_parent(dir).0;
+        }
+    }
+
+    /// Files tried for an import into the project that resolved to none of them
+    ///
+    /// `None` for imports that are not expected to name a project file: packages, standard
+    /// libraries, and the languages whose imports name packages or namespaces.
+    pub fn unresolved_candidates(&self, from_file: &str, import: &ImportInfo) -> Option<Vec<String>> {
+        let source = import.source.as_str();
+        let ext = from_file.rsplit_once('.').map(|(_, e)| e).unwrap_or("");
+        if manifest_of(from_file).is_some() {
+            return None;
+        }
+
+        let mut candidates = match ext {
+            "py" | "pyi" if source.starts_with('.') => self.python_candidates(from_file, source),
+            "rs" if import.kind == ImportKind::Module => rust_mod_candidates(from_file, source)?,
+            "go" | "cs" | "java" | "kt" | "kts" | "csproj" | "py" | "pyi" | "rs" => return None,
+            _ if source.starts_with('.') || self.is_project_specifier(from_file, source) => self
+                .script_paths(from_file, import)
+                .iter()
+                .flat_map(|path| candidate_files(path, import.kind))
+                .collect(),
+            _ => return None,
+        };
+        let mut seen = HashSet::new();
+        candidates.retain(|candidate| seen.insert(candidate.clone()));
+        Some(candidates)
+    }
+
+    /// Resolve a JavaScript/TypeScript or stylesheet import
+    fn resolve_script(&self, from_file: &str, import: &ImportInfo) -> Option<String> {
+        self.script_paths(from_file, import)
+            .iter()
+            .find_map(|path| find_matching_node(path, import.kind, self.node_ids, &self.dirs))
+    }
+
+    /// Paths a JavaScript/TypeScript or stylesheet import may point at, best first
+    ///
+    /// Relative paths resolve against the importing file; other specifiers go through
+    /// tsconfig/jsconfig `paths`, then bundler and `atomicflow.json` aliases, then `baseUrl`,
+    /// then the packages of the project's workspaces.
+    /// Anything left (`react`, `@vue/runtime-core`) is a package outside the project.
+    fn script_paths(&self, from_file: &str, import: &ImportInfo) -> Vec<String> {
+        let source = import.source.as_str();
+        let relative = || join_path(split_parent(from_file).0, source).into_iter().collect();
+
+        match import.kind {
+            ImportKind::Static | ImportKind::Dynamic | ImportKind::ReExport => {}
+            // Bare stylesheet imports are relative (Sass semantics); `~` points into node_modules
+            ImportKind::Style if !source.starts_with('~') && !source.starts_with('/') && !source.starts_with("@/") => {
+                return relative();
+            }
+            ImportKind::Style => {}
+            ImportKind::Module | ImportKind::Project => return Vec::new(),
+        }
+
+        if source.starts_with('.') {
+            return relative();
+        }
+
+        let ts_config = self.config.ts_config_for(from_file);
+        let mut paths = ts_config.map(|config| match_paths(&config.paths, source)).unwrap_or_default();
+        paths.extend(self.config.aliases_for(from_file).filter_map(|alias| alias.apply(source)));
+        paths.extend(ts_config.and_then(|config| join_path(config.base_url.as_deref()?, source)));
+        paths.extend(self.config.workspace_candidates(source));
+        for redirected in self.config.aliases_for(from_file).filter_map(|alias| alias.redirect(source)) {
+            paths.extend(self.config.workspace_candidates(&redirected));
+        }
+        paths.extend(self.conventional_src_path(from_file, source));
+        paths
+    }
+
+    /// `@/x` => `src/x`, the alias Vue CLI and Nuxt set up without declaring it
+    ///
+    /// Only for files no tsconfig covers and no alias maps `@/` for: a declared
+    /// mapping that finds nothing must not fall back to the root `src/`.
+    fn conventional_src_path(&self, from_file: &str, source: &str) -> Option<String> {
+        let rest = source.strip_prefix("@/")?;
+        let declared = self.config.ts_config_for(from_file).is_some()
+            || self
+                .config
+                .aliases_for(from_file)
+                .any(|alias| alias.apply(source).is_some() || alias.redirect(source).is_some());
+        (!declared).then(|| format!("src/{}", rest))
+    }
+
+    /// Whether a bare specifier is mapped into the project: by tsconfig `paths` (other than
+    /// a catch-all `*`), a path alias, the name of a workspace package (possibly through a
+    /// package alias) or the conventional `@/` where nothing declares it
+    fn is_project_specifier(&self, from_file: &str, source: &str) -> bool {
+        let in_paths = self.config.ts_config_for(from_file).is_some_and(|config| {
+            config.paths.iter().any(|(pattern, _)| match pattern.split_once('*') {
+                Some((prefix, suffix)) => !prefix.is_empty() && source.starts_with(prefix) && source.ends_with(suffix),
+                None => pattern == source,
+            })
+        });
+        in_paths
+            || self.conventional_src_path(from_file, source).is_some()
+            || self.config.aliases_for(from_file).any(|alias| alias.apply(source).is_some())
+            || self.is_workspace_specifier(source)
+            || self
+                .config
+                .aliases_for(from_file)
+                .filter_map(|alias| alias.redirect(source))
+                .any(|redirected| self.is_workspace_specifier(&redirected))
+    }
+
+    fn is_workspace_specifier(&self, source: &str) -> bool {
+        split_package_specifier(source).is_some_and(|(name, _)| self.config.is_workspace_package(name))
+    }
+
+    /// Resolve a dotted Python module (`a.b`, `.x.y`, `..z`) to a module file
+    ///
+    /// Imported names may be attributes rather than submodules, so the longest
+    /// module prefix that exists as `name.py` or `name/__init__.py` wins.
+    fn resolve_python(&self, from_file: &str, module: &str) -> Option<String> {
+        self.python_candidates(from_file, module)
+            .into_iter()
+            .find(|c| self.node_ids.contains(c))
+    }
+
+    /// Module files a dotted Python module may be, best first
+    fn python_candidates(&self, from_file: &str, module: &str) -> Vec<String> {
+        let level = module.chars().take_while(|c| *c == '.').count();
+        let parts: Vec<&str> = module[level..].split('.').filter(|p| !p.is_empty()).collect();
+
+        let bases: Vec<String> = if level > 0 {
+            // `.` is the importing file's package, each extra dot goes one level up
+            let mut dir: Vec<&str> = from_file.split('/').collect();
+            dir.pop();
+            for _ in 1..level {
+                if dir.pop().is_none() {
+                    return Vec::new();
+                }
+            }
+            vec![dir.join("/")]
+        } else {
+            self.source_roots.clone()
+        };
+
+        let min_len = if level > 0 { 0 } else { 1 };
+        let mut candidates = Vec::new();
+        for base in &bases {
+            for len in (min_len..=parts.len()).rev() {
+                let mut segments: Vec<&str> = Vec::new();
+                if !base.is_empty() {
+                    segments.push(base);
+                }
+                segments.extend(&parts[..len]);
+                let module_path = segments.join("/");
+                let package_init = if module_path.is_empty() {
+                    "__init__.py".to_string()
+                } else {
+                    format!("{}/__init__.py", module_path)
+                };
+
+                if len > 0 {
+                    candidates.push(format!("{}.py", module_path));
+                }
+                candidates.push(package_init);
+                if len > 0 {
+                    candidates.push(format!("{}.pyi", module_path));
+                }
+            }
+        }
+
+        candidates
+    }
+}
+
+impl Resolver<'_> {
+    /// Resolve `mod name;` (or its `#[path]` value) to the module file
+    fn resolve_rust_mod(&self, from_file: &str, source: &str) -> Option<String> {
+        rust_mod_candidates(from_file, source)?
+            .into_iter()
+            .find(|c| self.node_ids.contains(c))
+    }
+
+    /// Resolve a `use` path (`crate::a::B`, `super::x`, `other_crate::y`) to a module file
+    fn resolve_rust_path(&self, from_file: &str, source: &str) -> Option<String> {
+        let segments: Vec<&str> = source.split("::").filter(|s| !s.is_empty()).collect();
+        let first = *segments.first()?;
+        if RUST_STD_CRATES.contains(&first) {
+            return None;
+        }
+
+        let tree = self.rust_module_tree(from_file);
+
+        if let Some(tree) = &tree {
+            let current = tree.module_path(from_file);
+            let relative = match first {
+                "crate" => Some(segments[1..].to_vec()),
+                "self" | "super" => {
+                    let mut path = current.clone();
+                    let mut rest = &segments[..];
+                    while let Some((&head, tail)) = rest.split_first() {
+                        match head {
+                            "self" => {}
+                            "super" => {
+                                path.pop()?;
+                            }
+                            _ => break,
+                        }
+                        rest = tail;
+                    }
+                    path.extend_from_slice(rest);
+                    Some(path)
+                }
+                // 2018 uniform paths: `use child::Item` where `child` is a submodule
+                _ => {
+                    let mut path = current.clone();
+                    path.push(first);
+                    self.rust_module_file(tree, &path, false).map(|_| {
+                        path.extend_from_slice(&segments[1..]);
+                        path
+                    })
+                }
+            };
+
+            if let Some(path) = relative {
+                return self.rust_module_file(tree, &path, true);
+            }
+        }
+
+        // Another crate of the workspace
+        let crate_dir = self.crates.get(first)?;
+        let tree = RustModuleTree {
+            base: join_path(crate_dir, "src")?,
+            root_file: None,
+        };
+        self.rust_module_file(&tree, &segments[1..], true)
+    }
+
+    /// Module tree (base directory and crate root) that `file` belongs to
+    fn rust_module_tree(&self, file: &str) -> Option<RustModuleTree> {
+        let mut dir = split_parent(file).0;
+        let crate_dir = loop {
+            if self.node_ids.contains(&join_path(dir, "Cargo.toml")?) {
+                break dir;
+            }
+            if dir.is_empty() {
+                return None;
+            }
+            dir = split_parent(dir).0;
+        };
+
+        let relative = file
+            .strip_prefix(crate_dir)
+            .map(|r| r.trim_start_matches('/'))
+            .unwrap_or(file);
+        let parts: Vec<&str> = relative.split('/').collect();
+
+        // Binaries, tests, examples and benches are separate crate roots
+        let target_dir_len = match parts.as_slice() {
+            ["src", "bin", ..] => 2,
+            ["tests" | "examples" | "benches", ..] => 1,
+            _ => {
+                return Some(RustModuleTree {
+                    base: join_path(crate_dir, "src")?,
+                    root_file: None,
+                })
+            }
+        };
+
+        let target_dir = parts[..target_dir_len].join("/");
+        if parts.len() == target_dir_len + 1 {
+            // Single-file target: the file is its own crate root
+            return Some(RustModuleTree {
+                base: join_path(crate_dir, &target_dir)?,
+                root_file: Some(file.to_string()),
+            });
+        }
+
+        // Multi-file target: `src/bin/tool/main.rs`
+        let target_base = join_path(crate_dir, &parts[..=target_dir_len].join("/"))?;
+        Some(RustModuleTree {
+            root_file: Some(format!("{}/main.rs", target_base)),
+            base: target_base,
+        })
+    }
+
+    /// File defining the module at `path`, trying the longest existing prefix
+    ///
+    /// Trailing segments may name items rather than modules, so `a::b::Item`
+    /// resolves to `a/b.rs`; with `fallback_to_root`, paths naming crate-root
+    /// items resolve to the crate root file.
+    fn rust_module_file(&self, tree: &RustModuleTree, path: &[&str], fallback_to_root: bool) -> Option<String> {
+        let min_len = if fallback_to_root { 1 } else { path.len() };
+        for len in (min_len.max(1)..=path.len()).rev() {
+            let module_path = join_path(&tree.base, &path[..len].join("/"))?;
+            if let Some(found) = [format!("{}.rs", module_path), format!("{}/mod.rs", module_path)]
+                .into_iter()
+                .find(|c| self.node_ids.contains(c))
+            {
+                return Some(found);
+            }
+        }
+
+        if !fallback_to_root {
+            return None;
+        }
+        match &tree.root_file {
+            Some(root) => Some(root.clone()),
+            None => ["lib.rs", "main.rs"]
+                .into_iter()
+                .filter_map(|f| join_path(&tree.base, f))
+                .find(|c| self.node_ids.contains(c)),
+        }
+    }
+
+    /// Resolve a Go import path to its package directory via the owning `go.mod`
+    /// (the Go files of the project root for a root-level module's root package)
+    fn resolve_go_package(&self, import_path: &str) -> Vec<String> {
+        let module = self
+            .go_modules
+            .iter()
+            .filter(|(module, _)| {
+                import_path == module
+                    || import_path.strip_prefix(module.as_str()).is_some_and(|r| r.starts_with('/'))
+            })
+            .max_by_key(|(module, _)| module.len());
+        let Some(package_dir) = module.and_then(|(module, dir)| join_path(dir, &import_path[module.len()..])) else {
+            return Vec::new();
+        };
+
+        if package_dir.is_empty() {
+            // The project root has no directory node; its files stand for the package (see `link_source`)
+            let mut files: Vec<String> = self
+                .node_ids
+                .iter()
+                .filter(|id| !id.contains('/') && id.ends_with(".go") && !id.ends_with("_test.go"))
+                .cloned()
+                .collect();
+            files.sort();
+            return files;
+        }
+        self.node_ids.contains(&package_dir).then_some(package_dir).into_iter().collect()
+    }
+
+    /// Resolve a C# `using` target to the files declaring that namespace
+    ///
+    /// `using static` and alias targets name a type, so `A.B.Type` falls back to
+    /// `Type.cs` in namespace `A.B`. Anything else no file declares maps to the
+    /// project whose root namespace contains it, or else to every file of `A.B`.
+    fn resolve_csharp_using(&self, target: &str) -> Vec<String> {
+        if let Some(files) = self.cs_namespaces.get(target) {
+            return files.clone();
+        }
+
+        // `using static` and alias targets name a type: prefer its `Type.cs`
+        let type_namespace = target.rsplit_once('.').and_then(|(namespace, type_name)| {
+            let files = self.cs_namespaces.get(namespace)?;
+            let type_file = format!("{}.cs", type_name);
+            Some((files, files.iter().find(|f| split_parent(f).1 == type_file)))
+        });
+        if let Some((_, Some(file))) = type_namespace {
+            return vec![file.clone()];
+        }
+
+        let project = self
+            .cs_projects
+            .iter()
+            .filter(|(root, _)| {
+                target == root || target.strip_prefix(root.as_str()).is_some_and(|r| r.starts_with('.'))
+            })
+            .max_by_key(|(root, _)| root.len());
+
+        match (project, type_namespace) {
+            (Some((_, project)), _) => vec![project.clone()],
+            (None, Some((files, _))) => files.clone(),
+            (None, None) => Vec::new(),
+        }
+    }
+
+    /// Resolve a Java/Kotlin import to the class files it names
+    ///
+    /// Classes live in `<root>/<package path>/<Class>.java|.kt`; static and nested
+    /// imports (`a.b.C.member`) resolve through their longest class prefix, and
+    /// wildcards to every file of the package. Kotlin files need not mirror their
+    /// package in the directory layout, so the package index is checked as well.
+    fn resolve_jvm_import(&self, from_file: &str, import: &ImportInfo) -> Vec<String> {
+        let parts: Vec<&str> = import.source.split('.').collect();
+
+        if import.is_namespace {
+            if let Some(files) = self.jvm_packages.get(&import.source) {
+                return files.iter().filter(|f| *f != from_file).cloned().collect();
+            }
+        }
+
+        for len in (1..=parts.len()).rev() {
+            let class_path = parts[..len].join("/");
+            for root in &self.jvm_roots {
+                let Some(base) = join_path(root, &class_path) else {
+                    continue;
+                };
+                if let Some(found) = [format!("{}.java", base), format!("{}.kt", base)]
+                    .into_iter()
+                    .find(|c| self.node_ids.contains(c))
+                {
+                    return vec![found];
+                }
+            }
+
+            let package = parts[..len - 1].join(".");
+            let class_file = parts[len - 1];
+            if let Some(found) = self.jvm_packages.get(&package).and_then(|files| {
+                files.iter().find(|f| {
+                    let name = split_parent(f).1;
+                    name.strip_suffix(".kt").or_else(|| name.strip_suffix(".java")) == Some(class_file)
+                })
+            }) {
+                return vec![found.clone()];
+            }
+        }
+
+        // Kotlin top-level functions and properties: `import a.b.helper`
+        if from_file.ends_with(".kt") || from_file.ends_with(".kts") {
+            if let Some(files) = parts
+                .len()
+                .checked_sub(1)
+                .and_then(|len| self.jvm_packages.get(&parts[..len].join(".")))
+            {
+                return files.clone();
+            }
+        }
+
+        Vec::new()
+    }
+
+    /// Resolve a project-level reference (workspace member, `path` dependency,
+    /// local `replace`) to the manifest of the same kind in the referenced directory
+    fn resolve_manifest_reference(&self, from_file: &str, source: &str) -> Vec<String> {
+        let Some((dir, manifest)) = manifest_of(from_file) else {
+            return Vec::new();
+        };
+        let Some(target) = join_path(dir, source).and_then(|p| join_path(&p, manifest)) else {
+            return Vec::new();
+        };
+
+        let mut targets: Vec<String> = if target.contains('*') {
+            self.node_ids
+                .iter()
+                .filter(|id| wildcard_match(&target, id))
+                .cloned()
+                .collect()
+        } else if self.node_ids.contains(&target) {
+            vec![target]
+        } else {
+            Vec::new()
+        };
+        targets.sort();
+        targets
+    }
+}
+
+/// Module tree of one Rust crate target
+struct RustModuleTree {
+    /// Directory holding the crate root (e.g. `src`)
+    base: String,
+    /// Crate root file when it is not `lib.rs`/`main.rs` in `base`
+    root_file: Option<String>,
+}
+
+impl RustModuleTree {
+    /// Module path of a file inside this tree (`src/a/b.rs` => `[a, b]`)
+    fn module_path<'f>(&self, file: &'f str) -> Vec<&'f str> {
+        if self.root_file.as_deref() == Some(file) {
+            return Vec::new();
+        }
+
+        let relative = file
+            .strip_prefix(self.base.as_str())
+            .map(|r| r.trim_start_matches('/'))
+            .unwrap_or(file);
+        let mut path: Vec<&str> = relative.trim_end_matches(".rs").split('/').collect();
+
+        let is_root = path.len() == 1 && matches!(path[0], "lib" | "main");
+        if is_root || path.last() == Some(&"mod") {
+            path.pop();
+        }
+        path
+    }
+}
+
+/// Files `mod name;` (or its `#[path]` value) may declare, best first
+fn rust_mod_candidates(from_file: &str, source: &str) -> Option<Vec<String>> {
+    let (dir, file_name) = split_parent(from_file);
+
+    if source.ends_with(".rs") {
+        // #[path] is relative to the declaring file's directory
+        return Some(vec![join_path(dir, source)?]);
+    }
+
+    // `foo.rs` declares its children in `foo/`, mod-rs files in their own directory
+    let module_dir = if is_mod_rs_file(from_file) {
+        dir.to_string()
+    } else {
+        join_path(dir, file_name.trim_end_matches(".rs"))?
+    };
+    let module_path = join_path(&module_dir, source)?;
+    Some(vec![format!("{}.rs", module_path), format!("{}/mod.rs", module_path)])
+}
+
+/// Whether `mod foo;` in this file looks for `foo.rs` next to it (mod.rs, crate roots)
+fn is_mod_rs_file(file: &str) -> bool {
+    let (dir, file_name) = split_parent(file);
+
+    // Single-file binaries, tests, examples and benches are crate roots too
+    matches!(file_name, "mod.rs" | "lib.rs" | "main.rs" | "build.rs")
+        || matches!(split_parent(dir).1, "bin" | "tests" | "examples" | "benches")
+}
+
+fn is_jvm_file(id: &str) -> bool {
+    id.ends_with(".java") || id.ends_with(".kt") || id.ends_with(".kts")
+}
+
+/// Source root implied by a Java/Kotlin file's location and package
+/// (`app/src/main/java/com/acme/Foo.java` in `com.acme` => `app/src/main/java`)
+fn jvm_source_root(file: &str, package: &str) -> Option<String> {
+    let dir = split_parent(file).0;
+    let package_dir = package.replace('.', "/");
+
+    if dir == package_dir {
+        Some(String::new())
+    } else {
+        dir.strip_suffix(&package_dir)?.strip_suffix('/').map(str::to_string)
+    }
+}
+
+/// Directory and file name of a manifest node id, or `None` for other files
+fn manifest_of(id: &str) -> Option<(&str, &str)> {
+    let (dir, file_name) = split_parent(id);
+    MANIFEST_FILES.contains(&file_name).then_some((dir, file_name))
+}
+
+/// Split a node id into its parent directory and file name
+pub fn split_parent(id: &str) -> (&str, &str) {
+    id.rsplit_once('/').unwrap_or(("", id))
+}
+
+/// Join a relative path onto a directory, normalizing `.` and `..`
+/// Returns `None` when the path escapes the project root
+pub fn join_path(dir: &str, relative: &str) -> Option<String> {
+    let mut parts: Vec<&str> = dir.split('/').filter(|s| !s.is_empty()).collect();
+
+    for part in relative.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop()?;
+            }
+            other => parts.push(other),
+        }
+    }
+
+    Some(parts.join("/"))
+}
+
+/// Match a path against a pattern where `*` matches within one path segment
+fn wildcard_match(pattern: &str, path: &str) -> bool {
+    let pattern_parts: Vec<&str> = pattern.split('/').collect();
+    let path_parts: Vec<&str> = path.split('/').collect();
+
+    pattern_parts.len() == path_parts.len()
+        && pattern_parts.iter().zip(&path_parts).all(|(p, s)| match p.split_once('*') {
+            Some((prefix, suffix)) => {
+                s.len() >= prefix.len() + suffix.len() && s.starts_with(prefix) && s.ends_with(suffix)
+            }
+            None => p == s,
+        })
+}
+
+/// Targets of the `paths` pattern matching `specifier`, with `*` substituted
+///
+/// Like TypeScript, an exact pattern wins over wildcards, and among wildcards
+/// the one with the longest prefix.
+fn match_paths(paths: &[(String, Vec<String>)], specifier: &str) -> Vec<String> {
+    let mut best: Option<(usize, &str, &[String])> = None;
+    for (pattern, targets) in paths {
+        let matched = match pattern.split_once('*') {
+            Some((prefix, suffix)) => specifier
+                .strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix(suffix))
+                .map(|captured| (prefix.len(), captured)),
+            None => (pattern == specifier).then_some((usize::MAX, "")),
+        };
+        if let Some((score, captured)) = matched {
+            if best.is_none_or(|(best_score, _, _)| score > best_score) {
+                best = Some((score, captured, targets));
+            }
+        }
+    }
+
+    best.map(|(_, captured, targets)| targets.iter().map(|t| t.replacen('*', captured, 1)).collect())
+        .unwrap_or_default()
+}
+
+/// Find matching node for an import path
+///
+/// Directories are never matched themselves; `./components` means `./components/index.ts`.
+fn find_matching_node(
+    import_path: &str,
+    kind: ImportKind,
+    node_ids: &HashSet<String>,
+    dirs: &HashSet<String>,
+) -> Option<String> {
+    candidate_files(import_path, kind)
+        .into_iter()
+        .find(|candidate| node_ids.contains(candidate) && !dirs.contains(candidate))
+}
+
+/// Extensions tried after a script import path, best first
+const SCRIPT_EXTENSIONS: [&str; 15] = [
+    "",
+    ".ts",
+    ".tsx",
+    ".js",
+    ".jsx",
+    ".mts",
+    ".cts",
+    ".mjs",
+    ".cjs",
+    ".vue",
+    "/index.ts",
+    "/index.tsx",
+    "/index.js",
+    "/index.jsx",
+    "/index.vue",
+];
+
+/// TypeScript ESM imports name the emitted file: `./b.js` is written as `./b.ts`
+const TS_SOURCE_EXTENSIONS: [(&str, &[&str]); 4] = [
+    (".js", &[".ts", ".tsx"]),
+    (".jsx", &[".tsx"]),
+    (".mjs", &[".mts"]),
+    (".cjs", &[".cts"]),
+];
+
+/// Files an import path may refer to, best first: the path itself, its TypeScript source,
+/// then with the usual extensions and index files
+fn candidate_files(import_path: &str, kind: ImportKind) -> Vec<String> {
+    let extensions: &[&str] = match kind {
+        ImportKind::Style => &["", ".css", ".scss", ".sass", ".less"],
+        _ => &SCRIPT_EXTENSIONS,
+    };
+    let mut candidates: Vec<String> = extensions.iter().map(|ext| format!("{}{}", import_path, ext)).collect();
+
+    if kind != ImportKind::Style {
+        let sources = TS_SOURCE_EXTENSIONS.iter().find_map(|(emitted, sources)| {
+            let stem = import_path.strip_suffix(emitted)?;
+            Some(sources.iter().map(move |ext| format!("{}{}", stem, ext)))
+        });
+        if let Some(sources) = sources {
+            candidates.splice(1..1, sources);
+        }
+    }
+
+    // Sass partials: `@use 'tokens'` may refer to `_tokens.scss`
+    if kind == ImportKind::Style {
+        let (dir, file) = import_path.rsplit_once('/').unwrap_or(("", import_path));
+        let partial = if dir.is_empty() { format!("_{}", file) } else { format!("{}/_{}", dir, file) };
+        candidates.extend(extensions.iter().map(|ext| format!("{}{}", partial, ext)));
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config_module::{Alias, TsConfig};
+
+    fn ids(paths: &[&str]) -> HashSet<String> {
+        paths.iter().map(|p| p.to_string()).collect()
+    }
+
+    fn import_of(source: &str) -> ImportInfo {
+        ImportInfo {
+            source: source.to_string(),
+            is_default: false,
+            is_namespace: false,
+            line: 1,
+            kind: ImportKind::Static,
+            specifiers: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_resolve_python_modules() {
+        let node_ids = ids(&[
+            "app/__init__.py",
+            "app/models.py",
+            "app/services/__init__.py",
+            "app/services/auth.py",
+            "lib/shared/__init__.py",
+            "lib/shared/utils.py",
+        ]);
+        let resolver = Resolver::new(&node_ids, &HashMap::new(), &["lib".to_string()]);
+        let from = "app/services/auth.py";
+
+        // Absolute import of a module and of an attribute inside it
+        assert_eq!(resolver.resolve(from, &import_of("app.models")), vec!["app/models.py"]);
+        assert_eq!(resolver.resolve(from, &import_of("app.models.User")), vec!["app/models.py"]);
+        // Relative imports, including the package itself
+        assert_eq!(resolver.resolve(from, &import_of("..models")), vec!["app/models.py"]);
+        assert_eq!(resolver.resolve(from, &import_of(".helper")), vec!["app/services/__init__.py"]);
+        // Configured source root
+        assert_eq!(resolver.resolve(from, &import_of("shared.utils")), vec!["lib/shared/utils.py"]);
+        // Third-party modules stay unresolved
+        assert!(resolver.resolve(from, &import_of("requests")).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_style_partial() {
+        let node_ids = ids(&["src/styles/_tokens.scss"]);
+        let resolver = Resolver::new(&node_ids, &HashMap::new(), &[]);
+        let import = ImportInfo {
+            kind: ImportKind::Style,
+            ..import_of("../styles/tokens")
+        };
+
+        assert_eq!(resolver.resolve("src/components/App.vue", &import), vec!["src/styles/_tokens.scss"]);
+    }
+
+    #[test]
+    fn test_resolve_script_extensions() {
+        let node_ids = ids(&["src/b.ts", "src/View.tsx", "src/Comp.vue", "src/m.mts", "src/c.cts", "src/ui/index.jsx"]);
+        let resolver = Resolver::new(&node_ids, &HashMap::new(), &[]);
+        let resolve = |source: &str| resolver.resolve("src/main.ts", &import_of(source));
+
+        // NodeNext style: the import names the emitted `.js`
+        assert_eq!(resolve("./b.js"), vec!["src/b.ts"]);
+        assert_eq!(resolve("./View.js"), vec!["src/View.tsx"]);
+        assert_eq!(resolve("./m.mjs"), vec!["src/m.mts"]);
+        assert_eq!(resolve("./c.cjs"), vec!["src/c.cts"]);
+        assert_eq!(resolve("./Comp"), vec!["src/Comp.vue"]);
+        assert_eq!(resolve("./ui"), vec!["src/ui/index.jsx"]);
+        assert_eq!(resolver.unresolved_candidates("src/main.ts", &import_of("./Comp")).unwrap().len(), 15);
+    }
+
+    #[test]
+    fn test_resolve_ts_config_paths() {
+        let node_ids = ids(&[
+            "apps/web/src/components/Button.tsx",
+            "apps/web/src/utils/index.ts",
+            "libs/ui/src/index.ts",
+            "src/legacy.ts",
+        ]);
+        let config = ProjectConfig {
+            ts_configs: vec![TsConfig {
+                dir: "apps/web".to_string(),
+                base_url: Some("apps/web/src".to_string()),
+                paths: vec![
+                    ("@acme/ui".to_string(), vec!["libs/ui/src/index.ts".to_string()]),
+                    ("@/*".to_string(), vec!["apps/web/src/*".to_string()]),
+                    ("@/components/*".to_string(), vec!["apps/web/src/components/*".to_string()]),
+                ],
+            }],
+            ..Default::default()
+        };
+        let resolver = Resolver::new(&node_ids, &HashMap::new(), &[]).with_config(config);
+        let from = "apps/web/src/main.ts";
+
+        assert_eq!(resolver.resolve(from, &import_of("@acme/ui")), vec!["libs/ui/src/index.ts"]);
+        assert_eq!(resolver.resolve(from, &import_of("@/components/Button")), vec!["apps/web/src/components/Button.tsx"]);
+        // baseUrl
+        assert_eq!(resolver.resolve(from, &import_of("utils")), vec!["apps/web/src/utils/index.ts"]);
+        // Scoped packages without a matching pattern are not local files
+        assert!(resolver.resolve(from, &import_of("@vue/runtime-core")).is_empty());
+        assert!(resolver.resolve(from, &import_of("react")).is_empty());
+        // Files outside any tsconfig keep the `@/` => `src/` convention; files with one never fall back to it
+        assert_eq!(resolver.resolve("src/main.ts", &import_of("@/legacy")), vec!["src/legacy.ts"]);
+        assert!(resolver.resolve(from, &import_of("@/legacy")).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_aliases() {
+        let node_ids = ids(&["web/src/components/Card.vue", "shared/index.ts", "shared/dates.ts"]);
+        let alias = |find: &str, exact: bool, target: &str| Alias {
+            find: find.to_string(),
+            exact,
+            target: target.to_string(),
+            package: false,
+        };
+        let config = ProjectConfig {
+            bundler_aliases: vec![("web".to_string(), vec![alias("~components", false, "web/src/components")])],
+            manual_aliases: vec![alias("#shared", false, "shared"), alias("#", true, "shared/index.ts")],
+            ..Default::default()
+        };
+        let resolver = Resolver::new(&node_ids, &HashMap::new(), &[]).with_config(config);
+
+        assert_eq!(
+            resolver.resolve("web/src/App.vue", &import_of("~components/Card.vue")),
+            vec!["web/src/components/Card.vue"]
+        );
+        assert_eq!(resolver.resolve("web/src/App.vue", &import_of("#shared/dates")), vec!["shared/dates.ts"]);
+        assert_eq!(resolver.resolve("web/src/App.vue", &import_of("#")), vec!["shared/index.ts"]);
+        // Bundler aliases only apply below their config; `#shared` is not `#` + `/shared`
+        assert!(resolver.resolve("api/main.ts", &import_of("~components/Card.vue")).is_empty());
+        assert!(resolver.resolve("api/main.ts", &import_of("#/dates")).is_empty());
+    }
+
+    #[test]
+    fn test_external_packages() {
+        let node_ids = ids(&["src/main.ts", "Cargo.toml", "src/lib.rs", "tools/gen.rs"]);
+        let manifest = ParseResult {
+            dependencies: vec!["serde".to_string(), "serde_json".to_string()],
+            ..Default::default()
+        };
+        let parse_results = HashMap::from([("Cargo.toml".to_string(), manifest)]);
+        let config = ProjectConfig {
+            manual_aliases: vec![
+                Alias {
+                    find: "utils".to_string(),
+                    exact: false,
+                    target: "src/utils".to_string(),
+                    package: false,
+                },
+                Alias {
+                    find: "vue".to_string(),
+                    exact: true,
+                    target: "vue/dist/vue.esm.js".to_string(),
+                    package: true,
+                },
+            ],
+            ..Default::default()
+        };
+        let resolver = Resolver::new(&node_ids, &parse_results, &[]).with_config(config);
+        let external = |file: &str, source: &str| resolver.external_package(file, &import_of(source)).map(|p| p.id());
+
+        assert_eq!(external("src/main.ts", "lodash/debounce").as_deref(), Some("npm:lodash"));
+        assert_eq!(external("src/main.ts", "@tanstack/vue-query").as_deref(), Some("npm:@tanstack/vue-query"));
+        assert_eq!(external("src/main.ts", "fs/promises"), None);
+        assert_eq!(external("src/main.ts", "node:path"), None);
+        assert_eq!(external("src/main.ts", "@/missing"), None);
+        assert_eq!(external("src/main.ts", "utils/missing"), None);
+        assert_eq!(external("src/main.ts", "vue").as_deref(), Some("npm:vue"));
+        assert_eq!(resolver.unresolved_candidates("src/main.ts", &import_of("vue")), None);
+        assert_eq!(external("app/views.py", "requests.adapters").as_deref(), Some("pypi:requests"));
+        assert_eq!(external("app/views.py", "os.path"), None);
+        assert_eq!(external("src/lib.rs", "serde::Deserialize").as_deref(), Some("crates:serde"));
+        assert_eq!(external("tools/gen.rs", "serde_json::json").as_deref(), Some("crates:serde_json"));
+        // Not a dependency: a module the resolver missed, not a crate
+        assert_eq!(external("src/lib.rs", "bindings::Api"), None);
+        assert_eq!(external("src/lib.rs", "std::fmt"), None);
+        assert_eq!(external("src/lib.rs", "crate::graph"), None);
+    }
+
+    #[test]
+    fn test_unresolved_candidates() {
+        let node_ids = ids(&["src/main.ts", "app/__init__.py", "src/lib.rs"]);
+        let resolver = Resolver::new(&node_ids, &HashMap::new(), &[]);
+        let unresolved = |file: &str, import: ImportInfo| resolver.unresolved_candidates(file, &import);
+
+        let candidates = unresolved("src/main.ts", import_of("./utils")).unwrap();
+        assert_eq!(candidates[..3], ["src/utils", "src/utils.ts", "src/utils.tsx"]);
+        assert_eq!(candidates.last().map(String::as_str), Some("src/utils/index.vue"));
+        assert_eq!(unresolved("src/main.ts", import_of("@/store")).unwrap()[1], "src/store.ts");
+        assert_eq!(
+            unresolved("app/views.py", import_of(".models")).unwrap(),
+            vec!["app/models.py", "app/models/__init__.py", "app/models.pyi", "app/__init__.py"]
+        );
+        let module = ImportInfo { kind: ImportKind::Module, ..import_of("graph") };
+        assert_eq!(unresolved("src/lib.rs", module).unwrap(), vec!["src/graph.rs", "src/graph/mod.rs"]);
+        // Packages are not expected to resolve to project files
+        assert_eq!(unresolved("src/main.ts", import_of("react")), None);
+        assert_eq!(unresolved("app/views.py", import_of("requests")), None);
+    }
+
+    #[test]
+    fn test_resolve_rust_module_tree() {
+        let node_ids = ids(&[
+            "Cargo.toml",
+            "engine/Cargo.toml",
+            "engine/src/main.rs",
+            "engine/src/parser.rs",
+            "engine/src/parser/python.rs",
+            "engine/src/layout/mod.rs",
+            "engine/src/generated/bindings.rs",
+            "core/Cargo.toml",
+            "core/src/lib.rs",
+            "core/src/graph.rs",
+        ]);
+        let mut parse_results = HashMap::new();
+        parse_results.insert(
+            "core/Cargo.toml".to_string(),
+            ParseResult { packages: vec!["flow_core".to_string()], ..Default::default() },
+        );
+        let resolver = Resolver::new(&node_ids, &parse_results, &[]);
+        let module = |source: &str| ImportInfo { kind: ImportKind::Module, ..import_of(source) };
+        let project = |source: &str| ImportInfo { kind: ImportKind::Project, ..import_of(source) };
+
+        // mod declarations: foo.rs, foo/mod.rs, non-mod-rs children and #[path]
+        assert_eq!(resolver.resolve("engine/src/main.rs", &module("parser")), vec!["engine/src/parser.rs"]);
+        assert_eq!(resolver.resolve("engine/src/main.rs", &module("layout")), vec!["engine/src/layout/mod.rs"]);
+        assert_eq!(resolver.resolve("engine/src/parser.rs", &module("python")), vec!["engine/src/parser/python.rs"]);
+        assert_eq!(
+            resolver.resolve("engine/src/main.rs", &module("generated/bindings.rs")),
+            vec!["engine/src/generated/bindings.rs"]
+        );
+
+        // use paths: crate, super, uniform paths and workspace crates
+        let from = "engine/src/parser/python.rs";
+        assert_eq!(resolver.resolve(from, &import_of("crate::layout::Sugiyama")), vec!["engine/src/layout/mod.rs"]);
+        assert_eq!(resolver.resolve(from, &import_of("super::ImportInfo")), vec!["engine/src/parser.rs"]);
+        assert_eq!(resolver.resolve(from, &import_of("crate::Args")), vec!["engine/src/main.rs"]);
+        assert_eq!(resolver.resolve("engine/src/main.rs", &import_of("parser::python")), vec!["engine/src/parser/python.rs"]);
+        assert_eq!(resolver.resolve(from, &import_of("flow_core::graph::Graph")), vec!["core/src/graph.rs"]);
+        assert!(resolver.resolve(from, &import_of("std::collections::HashMap")).is_empty());
+        assert!(resolver.resolve(from, &import_of("serde::Serialize")).is_empty());
+
+        // Workspace members and path dependencies link manifests
+        assert_eq!(resolver.resolve("Cargo.toml", &project("*")), vec!["core/Cargo.toml", "engine/Cargo.toml"]);
+        assert_eq!(resolver.resolve("engine/Cargo.toml", &project("../core")), vec!["core/Cargo.toml"]);
+    }
+
+    #[test]
+    fn test_resolve_go_packages() {
+        let node_ids = ids(&[
+            "go.mod",
+            "app.go",
+            "app_test.go",
+            "version.go",
+            "cmd",
+            "cmd/server",
+            "cmd/server/main.go",
+            "internal",
+            "internal/store",
+            "internal/store/db.go",
+            "shared/go.mod",
+            "shared/auth",
+            "shared/auth/token.go",
+        ]);
+        let mut parse_results = HashMap::new();
+        for (manifest, module) in [("go.mod", "github.com/acme/app"), ("shared/go.mod", "github.com/acme/shared")] {
+            parse_results.insert(
+                manifest.to_string(),
+                ParseResult { packages: vec![module.to_string()], ..Default::default() },
+            );
+        }
+        let resolver = Resolver::new(&node_ids, &parse_results, &[]);
+        let from = "cmd/server/main.go";
+
+        assert_eq!(resolver.link_source(from), "cmd/server");
+        assert_eq!(resolver.resolve(from, &import_of("github.com/acme/app/internal/store")), vec!["internal/store"]);
+        assert_eq!(resolver.resolve(from, &import_of("github.com/acme/shared/auth")), vec!["shared/auth"]);
+        assert!(resolver.resolve(from, &import_of("github.com/acme/application/x")).is_empty());
+        assert!(resolver.resolve(from, &import_of("fmt")).is_empty());
+        // The root package of a module at the project root
+        assert_eq!(resolver.link_source("app.go"), "app.go");
+        assert_eq!(resolver.resolve(from, &import_of("github.com/acme/app")), vec!["app.go", "version.go"]);
+
+        let replace = ImportInfo { kind: ImportKind::Project, ..import_of("./shared") };
+        assert_eq!(resolver.resolve("go.mod", &replace), vec!["shared/go.mod"]);
+    }
+
+    #[test]
+    fn test_resolve_jvm_imports() {
+        let node_ids = ids(&[
+            "app/src/main/java/com/acme/app/Main.java",
+            "shared/src/main/java/com/acme/shared/Guard.java",
+            "shared/src/main/java/com/acme/shared/Strings.java",
+            "web/src/main/kotlin/Routes.kt",
+            "web/src/main/kotlin/Helpers.kt",
+        ]);
+        let mut parse_results = HashMap::new();
+        for (file, package) in [
+            ("app/src/main/java/com/acme/app/Main.java", "com.acme.app"),
+            ("shared/src/main/java/com/acme/shared/Guard.java", "com.acme.shared"),
+            ("shared/src/main/java/com/acme/shared/Strings.java", "com.acme.shared"),
+            // Kotlin file whose directory does not mirror its package
+            ("web/src/main/kotlin/Routes.kt", "com.acme.web"),
+            ("web/src/main/kotlin/Helpers.kt", "com.acme.web"),
+        ] {
+            parse_results.insert(
+                file.to_string(),
+                ParseResult { packages: vec![package.to_string()], ..Default::default() },
+            );
+        }
+        let resolver = Resolver::new(&node_ids, &parse_results, &[]);
+        let from = "app/src/main/java/com/acme/app/Main.java";
+        let wildcard = |source: &str| ImportInfo { is_namespace: true, ..import_of(source) };
+
+        assert_eq!(
+            resolver.resolve(from, &import_of("com.acme.shared.Guard")),
+            vec!["shared/src/main/java/com/acme/shared/Guard.java"]
+        );
+        // Static member import resolves through its class
+        assert_eq!(
+            resolver.resolve(from, &import_of("com.acme.shared.Strings.trim")),
+            vec!["shared/src/main/java/com/acme/shared/Strings.java"]
+        );
+        assert_eq!(
+            resolver.resolve(from, &wildcard("com.acme.shared")),
+            vec!["shared/src/main/java/com/acme/shared/Guard.java", "shared/src/main/java/com/acme/shared/Strings.java"]
+        );
+        assert_eq!(resolver.resolve(from, &import_of("com.acme.web.Routes")), vec!["web/src/main/kotlin/Routes.kt"]);
+        assert!(resolver.resolve(from, &import_of("java.util.List")).is_empty());
+
+        // Kotlin top-level function import maps to its package
+        assert_eq!(
+            resolver.resolve("web/src/main/kotlin/Routes.kt", &import_of("com.acme.shared.requireText")),
+            vec!["shared/src/main/java/com/acme/shared/Guard.java", "shared/src/main/java/com/acme/shared/Strings.java"]
+        );
+    }
+
+    #[test]
+    fn test_resolve_csharp_namespaces() {
+        let node_ids = ids(&[
+            "Web/Web.csproj",
+            "Web/Program.cs",
+            "Shared/Shared.csproj",
+            "Shared/Models/Invoice.cs",
+            "Shared/Models/Customer.cs",
+            "Shared/Guard.cs",
+        ]);
+        let mut parse_results = HashMap::new();
+        for (file, namespace) in [
+            ("Web/Web.csproj", "Acme.Web"),
+            ("Shared/Shared.csproj", "Acme.Shared"),
+            ("Shared/Models/Invoice.cs", "Acme.Shared.Models"),
+            ("Shared/Models/Customer.cs", "Acme.Shared.Models"),
+            ("Shared/Guard.cs", "Acme.Shared"),
+        ] {
+            parse_results.insert(
+                file.to_string(),
+                ParseResult { packages: vec![namespace.to_string()], ..Default::default() },
+            );
+        }
+        let resolver = Resolver::new(&node_ids, &parse_results, &[]);
+        let from = "Web/Program.cs";
+
+        assert_eq!(
+            resolver.resolve(from, &import_of("Acme.Shared.Models")),
+            vec!["Shared/Models/Customer.cs", "Shared/Models/Invoice.cs"]
+        );
+        // `using static` / alias targets name a type
+        assert_eq!(resolver.resolve(from, &import_of("Acme.Shared.Models.Invoice")), vec!["Shared/Models/Invoice.cs"]);
+        // Namespaces without declaring files fall back to the owning project
+        assert_eq!(resolver.resolve(from, &import_of("Acme.Shared.Extensions")), vec!["Shared/Shared.csproj"]);
+        assert!(resolver.resolve(from, &import_of("System.Linq")).is_empty());
+
+        let reference = ImportInfo { kind: ImportKind::Project, ..import_of("../Shared/Shared.csproj") };
+        assert_eq!(resolver.resolve("Web/Web.csproj", &reference), vec!["Shared/Shared.csproj"]);
+    }
+}
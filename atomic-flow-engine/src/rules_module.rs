@@ -0,0 +1,150 @@
+//! Rules module for checking links against architecture rules
+//! Same rule format and defaults as the extension's `archLinter.ts` (`.python-live-rules.json`)
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::graph_module::Link;
+use crate::parser_module::ImportKind;
+
+/// Project-level rules file, read from the project root
+pub const RULES_FILE: &str = ".python-live-rules.json";
+
+/// Forbids links from files matching `from` to files matching `to`
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ArchRule {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    pub forbidden: ForbiddenLink,
+}
+
+/// Regex patterns (unanchored) matched against node ids
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct ForbiddenLink {
+    pub from: String,
+    pub to: String,
+}
+
+/// A link broken by a rule
+#[derive(Serialize, Debug, Clone)]
+pub struct Violation {
+    pub rule: String,
+    pub description: String,
+    pub source: String,
+    pub target: String,
+    pub kind: ImportKind,
+}
+
+/// Default rules for hexagonal architecture
+pub fn default_rules() -> Vec<ArchRule> {
+    let rule = |name: &str, description: &str, from: &str, to: &str| ArchRule {
+        name: name.to_string(),
+        description: description.to_string(),
+        forbidden: ForbiddenLink {
+            from: from.to_string(),
+            to: to.to_string(),
+        },
+    };
+    vec![
+        rule(
+            "Domain Independence",
+            "Domain layer cannot import from Infrastructure/Adapters",
+            ".*/(Domain|domain)/.*",
+            ".*/([Aa]dapters?|[Ii]nfrastructure)/.*",
+        ),
+        rule(
+            "No Reverse Dependencies",
+            "Infrastructure cannot import from Application layer",
+            ".*/([Ii]nfrastructure|adapters/driven)/.*",
+            ".*/([Aa]pplication|use[_-]?cases)/.*",
+        ),
+    ]
+}
+
+/// Rules from `file`, or from the project's rules file, or the defaults when neither exists
+pub fn load_rules(root: &Path, file: Option<&Path>) -> Result<Vec<ArchRule>> {
+    let path = match file {
+        Some(file) => file.to_path_buf(),
+        None => root.join(RULES_FILE),
+    };
+    if file.is_none() && !path.exists() {
+        return Ok(default_rules());
+    }
+
+    let content = fs::read_to_string(&path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Invalid rules in {}", path.display()))
+}
+
+/// Links forbidden by a rule; each link reports the first rule it breaks
+pub fn check_rules(links: &[Link], rules: &[ArchRule]) -> Result<Vec<Violation>> {
+    let compiled = rules
+        .iter()
+        .map(|rule| {
+            let from = Regex::new(&rule.forbidden.from).with_context(|| format!("Invalid `from` in rule {}", rule.name))?;
+            let to = Regex::new(&rule.forbidden.to).with_context(|| format!("Invalid `to` in rule {}", rule.name))?;
+            Ok((rule, from, to))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let violations = links
+        .iter()
+        .filter_map(|link| {
+            let (rule, _, _) = compiled
+                .iter()
+                .find(|(_, from, to)| from.is_match(&link.source) && to.is_match(&link.target))?;
+            Some(Violation {
+                rule: rule.name.clone(),
+                description: rule.description.clone(),
+                source: link.source.clone(),
+                target: link.target.clone(),
+                kind: link.kind,
+            })
+        })
+        .collect();
+    Ok(violations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn link(source: &str, target: &str) -> Link {
+        Link {
+            source: source.to_string(),
+            target: target.to_string(),
+            weight: Some(1),
+            kind: ImportKind::Static,
+            symbols: Vec::new(),
+            in_cycle: false,
+            breaks_cycle: false,
+        }
+    }
+
+    #[test]
+    fn test_check_default_rules() {
+        let links = vec![
+            link("src/domain/order.ts", "src/adapters/db.ts"),
+            link("src/infrastructure/repo.ts", "src/application/place_order.ts"),
+            link("src/application/place_order.ts", "src/domain/order.ts"),
+        ];
+
+        let violations = check_rules(&links, &default_rules()).unwrap();
+        let found: Vec<(&str, &str)> = violations.iter().map(|v| (v.rule.as_str(), v.source.as_str())).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Domain Independence", "src/domain/order.ts"),
+                ("No Reverse Dependencies", "src/infrastructure/repo.ts"),
+            ]
+        );
+
+        let custom: Vec<ArchRule> =
+            serde_json::from_str(r#"[{ "name": "No UI", "forbidden": { "from": "^src/", "to": "(" } }]"#).unwrap();
+        assert!(check_rules(&links, &custom).is_err());
+    }
+}
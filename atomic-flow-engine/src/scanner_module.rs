@@ -0,0 +1,224 @@
+//! Scanner module, the entry point for embedding the engine in other Rust tools
+//! `Scanner` configures and runs scans, `ProjectGraph` answers questions about the result
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Result};
+use serde::Serialize;
+
+use crate::analysis_module::find_reachable;
+use crate::cache_module::ParseCache;
+use crate::graph_module::{build_graph, Diagnostic, Graph, Link, Node, ScanOptions};
+
+/// Scans one project; rescans reuse the parse results of unchanged files
+///
+/// ```no_run
+/// use atomic_flow_engine::Scanner;
+///
+/// let graph = Scanner::new("path/to/project")
+///     .deps(true)
+///     .exclude("**/*.test.ts")
+///     .scan()?;
+/// for node in graph.dependents_of("src/api.ts") {
+///     println!("{}", node.source);
+/// }
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+pub struct Scanner {
+    root: PathBuf,
+    options: ScanOptions,
+    disk_cache: bool,
+    /// Created on the first scan that parses files
+    cache: Option<ParseCache>,
+}
+
+impl Scanner {
+    /// Scanner for the project at `root`: directory tree only, disk cache enabled
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            options: ScanOptions::default(),
+            disk_cache: true,
+            cache: None,
+        }
+    }
+
+    /// Replace all scan options at once
+    pub fn options(mut self, options: ScanOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Parse imports and build dependency links
+    pub fn deps(mut self, deps: bool) -> Self {
+        self.options.deps = deps;
+        self
+    }
+
+    /// Add a root for absolute imports, relative to the project
+    pub fn source_root(mut self, dir: impl Into<String>) -> Self {
+        self.options.source_roots.push(dir.into());
+        self
+    }
+
+    /// Add an entry point glob; files get `reachable` set
+    pub fn entry(mut self, glob: impl Into<String>) -> Self {
+        self.options.entries.push(glob.into());
+        self
+    }
+
+    /// Add the third-party packages imported as `external` nodes, with their versions
+    pub fn external(mut self, external: bool) -> Self {
+        self.options.external = external;
+        self
+    }
+
+    /// Only scan files matching this glob (repeatable)
+    pub fn include(mut self, glob: impl Into<String>) -> Self {
+        self.options.include.push(glob.into());
+        self
+    }
+
+    /// Skip files and directories matching this glob (repeatable)
+    pub fn exclude(mut self, glob: impl Into<String>) -> Self {
+        self.options.exclude.push(glob.into());
+        self
+    }
+
+    /// Whether parse results are persisted under `.atomicflow/cache`; without it
+    /// they are still kept in memory between scans of this scanner
+    pub fn disk_cache(mut self, enabled: bool) -> Self {
+        self.disk_cache = enabled;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Scan the project
+    pub fn scan(&mut self) -> Result<ProjectGraph> {
+        if !self.root.exists() {
+            bail!("Path does not exist: {}", self.root.display());
+        }
+
+        let cache = if self.options.deps || !self.options.entries.is_empty() || self.options.external {
+            let (root, disk_cache) = (&self.root, self.disk_cache);
+            Some(self.cache.get_or_insert_with(|| {
+                if disk_cache {
+                    ParseCache::load(root)
+                } else {
+                    ParseCache::in_memory()
+                }
+            }))
+        } else {
+            None
+        };
+
+        let graph = build_graph(&self.options, &self.root, cache)?;
+        Ok(ProjectGraph { graph })
+    }
+
+    /// Parse `path` again on the next scan even if its size and mtime are unchanged;
+    /// `path` is relative to the project or absolute
+    pub fn invalidate(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        if let Some(cache) = &mut self.cache {
+            cache.forget(&relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+}
+
+/// Result of a scan; serializes to the same JSON as the command line output
+#[derive(Serialize, Debug)]
+#[serde(transparent)]
+pub struct ProjectGraph {
+    graph: Graph,
+}
+
+impl ProjectGraph {
+    pub fn nodes(&self) -> &[Node] {
+        &self.graph.nodes
+    }
+
+    pub fn links(&self) -> &[Link] {
+        &self.graph.links
+    }
+
+    /// Node ids of each dependency cycle
+    pub fn cycles(&self) -> &[Vec<String>] {
+        &self.graph.cycles
+    }
+
+    /// Imports into the project that match no scanned file
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.graph.diagnostics
+    }
+
+    pub fn node(&self, id: &str) -> Option<&Node> {
+        self.graph.nodes.iter().find(|n| n.id == id)
+    }
+
+    /// Links from `id` to what it imports
+    pub fn imports_of<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a Link> {
+        self.graph.links.iter().filter(move |l| l.source == id)
+    }
+
+    /// Links from the nodes importing `id`
+    pub fn dependents_of<'a>(&'a self, id: &'a str) -> impl Iterator<Item = &'a Link> {
+        self.graph.links.iter().filter(move |l| l.target == id)
+    }
+
+    /// Ids of every node that imports `id` directly or through other nodes, sorted
+    pub fn transitive_dependents(&self, id: &str) -> Vec<String> {
+        let reversed = self.graph.links.iter().map(|l| (l.target.as_str(), l.source.as_str()));
+        let mut reached = find_reachable([id], reversed);
+        reached.remove(id);
+
+        let mut dependents: Vec<String> = reached.into_iter().collect();
+        dependents.sort();
+        dependents
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    pub fn into_graph(self) -> Graph {
+        self.graph
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_scanner_builder_and_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir(root.join("src")).unwrap();
+        fs::write(root.join("src/main.ts"), "import { api } from './api';\n").unwrap();
+        fs::write(root.join("src/api.ts"), "import { db } from './db';\nexport const api = 1;\n").unwrap();
+        fs::write(root.join("src/db.ts"), "export const db = 1;\n").unwrap();
+        fs::write(root.join("src/db.test.ts"), "import { db } from './db';\n").unwrap();
+
+        let mut scanner = Scanner::new(root).deps(true).exclude("**/*.test.ts").disk_cache(false);
+        let graph = scanner.scan().unwrap();
+
+        assert!(graph.node("src/db.test.ts").is_none());
+        assert_eq!(graph.node("src/api.ts").unwrap().exports, Some(1));
+        let imports: Vec<&str> = graph.imports_of("src/api.ts").map(|l| l.target.as_str()).collect();
+        assert_eq!(imports, vec!["src/db.ts"]);
+        let direct: Vec<&str> = graph.dependents_of("src/db.ts").map(|l| l.source.as_str()).collect();
+        assert_eq!(direct, vec!["src/api.ts"]);
+        assert_eq!(graph.transitive_dependents("src/db.ts"), vec!["src/api.ts", "src/main.ts"]);
+        assert!(!root.join(".atomicflow").exists());
+
+        // Without deps only the directory tree is listed
+        let tree = Scanner::new(root).scan().unwrap();
+        assert!(tree.links().is_empty() && tree.node("src/main.ts").unwrap().line_count.is_none());
+    }
+}
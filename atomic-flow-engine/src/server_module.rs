@@ -0,0 +1,292 @@
+//! Server module for driving the engine from an editor over stdin/stdout
+//! Speaks JSON-RPC 2.0 with one message per line and keeps the last scan in memory
+
+use std::collections::BTreeMap;
+use std::io::{BufRead, Write};
+use std::path::PathBuf;
+
+use anyhow::Result;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::watch_module::diff_graphs;
+use crate::{ProjectGraph, ScanOptions, Scanner};
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// Method needs a graph but `scan` has not succeeded yet
+const NOT_SCANNED: i64 = -32002;
+
+#[derive(Debug)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct ScanParams {
+    path: String,
+    #[serde(flatten)]
+    options: ScanOptions,
+}
+
+#[derive(Deserialize)]
+struct NodeParams {
+    id: String,
+    #[serde(default)]
+    transitive: bool,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct InvalidateParams {
+    /// Files to re-parse, relative to the project or absolute; empty re-checks every file
+    paths: Vec<String>,
+}
+
+/// The scanned project and the scanner that rebuilds it
+struct Project {
+    scanner: Scanner,
+    graph: ProjectGraph,
+}
+
+pub struct Server {
+    /// Whether parse results are persisted under `.atomicflow/cache`
+    disk_cache: bool,
+    project: Option<Project>,
+}
+
+/// Answer requests from stdin until it closes
+pub fn run(disk_cache: bool) -> Result<()> {
+    let mut server = Server::new(disk_cache);
+    let stdin = std::io::stdin();
+    let mut out = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle_message(&line) {
+            serde_json::to_writer(&mut out, &response)?;
+            out.write_all(b"\n")?;
+            out.flush()?;
+        }
+    }
+
+    Ok(())
+}
+
+impl Server {
+    pub fn new(disk_cache: bool) -> Self {
+        Self {
+            disk_cache,
+            project: None,
+        }
+    }
+
+    /// Response to one line of input, `None` for notifications
+    pub fn handle_message(&mut self, line: &str) -> Option<Value> {
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(err) => return Some(response(Value::Null, Err(RpcError::new(PARSE_ERROR, err.to_string())))),
+        };
+        let request: Request = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(err) => return Some(response(Value::Null, Err(RpcError::new(INVALID_REQUEST, err.to_string())))),
+        };
+
+        let result = self.dispatch(&request.method, request.params);
+        request.id.map(|id| response(id, result))
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "scan" => self.scan(parse_params(params)?),
+            "getNode" => self.get_node(parse_params(params)?),
+            "dependentsOf" => self.dependents_of(parse_params(params)?),
+            "layout" => self.layout(),
+            "invalidate" => self.invalidate(parse_params(params)?),
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Unknown method: {}", method))),
+        }
+    }
+
+    /// Scan a project from scratch; the dependency graph is always built
+    fn scan(&mut self, params: ScanParams) -> Result<Value, RpcError> {
+        let root = PathBuf::from(&params.path);
+        if !root.exists() {
+            return Err(RpcError::new(INVALID_PARAMS, format!("Path does not exist: {}", params.path)));
+        }
+
+        let mut scanner = Scanner::new(root)
+            .options(params.options)
+            .deps(true)
+            .disk_cache(self.disk_cache);
+        let graph = scanner.scan().map_err(internal)?;
+        let result = serde_json::to_value(&graph).map_err(internal)?;
+
+        self.project = Some(Project { scanner, graph });
+        Ok(result)
+    }
+
+    /// A node with its outgoing and incoming links
+    fn get_node(&self, params: NodeParams) -> Result<Value, RpcError> {
+        let graph = &self.project()?.graph;
+        let node = graph
+            .node(&params.id)
+            .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown node: {}", params.id)))?;
+
+        let imports: Vec<_> = graph.imports_of(&node.id).collect();
+        let dependents: Vec<_> = graph.dependents_of(&node.id).collect();
+        Ok(json!({ "node": node, "imports": imports, "dependents": dependents }))
+    }
+
+    /// Ids of the nodes importing `id`, directly or (with `transitive`) through other nodes
+    fn dependents_of(&self, params: NodeParams) -> Result<Value, RpcError> {
+        let graph = &self.project()?.graph;
+        if graph.node(&params.id).is_none() {
+            return Err(RpcError::new(INVALID_PARAMS, format!("Unknown node: {}", params.id)));
+        }
+
+        if params.transitive {
+            return Ok(json!(graph.transitive_dependents(&params.id)));
+        }
+        let mut dependents: Vec<&str> = graph.dependents_of(&params.id).map(|l| l.source.as_str()).collect();
+        dependents.sort();
+        dependents.dedup();
+        Ok(json!(dependents))
+    }
+
+    /// Node positions keyed by id
+    fn layout(&self) -> Result<Value, RpcError> {
+        let graph = &self.project()?.graph;
+        let positions: BTreeMap<&str, Value> = graph
+            .nodes()
+            .iter()
+            .map(|n| (n.id.as_str(), json!({ "x": n.x, "y": n.y })))
+            .collect();
+        Ok(json!(positions))
+    }
+
+    /// Re-parse the given files, rebuild and return the patches against the previous graph
+    fn invalidate(&mut self, params: InvalidateParams) -> Result<Value, RpcError> {
+        let project = self
+            .project
+            .as_mut()
+            .ok_or_else(|| RpcError::new(NOT_SCANNED, "No project scanned yet"))?;
+
+        for path in &params.paths {
+            project.scanner.invalidate(path);
+        }
+
+        let graph = project.scanner.scan().map_err(internal)?;
+        let patches = serde_json::to_value(diff_graphs(project.graph.graph(), graph.graph())).map_err(internal)?;
+        project.graph = graph;
+        Ok(json!({ "patches": patches }))
+    }
+
+    fn project(&self) -> Result<&Project, RpcError> {
+        self.project
+            .as_ref()
+            .ok_or_else(|| RpcError::new(NOT_SCANNED, "No project scanned yet"))
+    }
+}
+
+/// Deserialize `params`; omitted params count as an empty object
+fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    let params = if params.is_null() { json!({}) } else { params };
+    serde_json::from_value(params).map_err(|err| RpcError::new(INVALID_PARAMS, err.to_string()))
+}
+
+fn internal(err: impl std::fmt::Display) -> RpcError {
+    RpcError::new(INTERNAL_ERROR, err.to_string())
+}
+
+fn response(id: Value, result: Result<Value, RpcError>) -> Value {
+    match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(err) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": err.code, "message": err.message },
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_serve_requests() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::write(root.join("a.ts"), "import { b } from './b';\n").unwrap();
+        fs::write(root.join("b.ts"), "import { c } from './c';\nexport const b = 1;\n").unwrap();
+        fs::write(root.join("c.ts"), "export const c = 1;\n").unwrap();
+
+        let mut server = Server::new(false);
+        let mut call = |request: Value| server.handle_message(&request.to_string());
+
+        let not_scanned = call(json!({ "jsonrpc": "2.0", "id": 1, "method": "layout" })).unwrap();
+        assert_eq!(not_scanned["error"]["code"], NOT_SCANNED);
+
+        let scan = call(json!({ "jsonrpc": "2.0", "id": 2, "method": "scan", "params": { "path": root } })).unwrap();
+        assert_eq!(scan["result"]["links"].as_array().unwrap().len(), 2);
+
+        let direct = call(json!({ "jsonrpc": "2.0", "id": 3, "method": "dependentsOf", "params": { "id": "c.ts" } }));
+        assert_eq!(direct.unwrap()["result"], json!(["b.ts"]));
+        let transitive = call(json!({
+            "jsonrpc": "2.0", "id": 4, "method": "dependentsOf", "params": { "id": "c.ts", "transitive": true }
+        }));
+        assert_eq!(transitive.unwrap()["result"], json!(["a.ts", "b.ts"]));
+
+        let node = call(json!({ "jsonrpc": "2.0", "id": 5, "method": "getNode", "params": { "id": "b.ts" } })).unwrap();
+        assert_eq!(node["result"]["imports"][0]["target"], "c.ts");
+        assert_eq!(node["result"]["dependents"][0]["source"], "a.ts");
+
+        fs::write(root.join("a.ts"), "import { c } from './c';\n").unwrap();
+        let patches = call(json!({
+            "jsonrpc": "2.0", "id": 6, "method": "invalidate", "params": { "paths": ["a.ts"] }
+        }));
+        let ops: Vec<Value> = patches.unwrap()["result"]["patches"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["op"].clone())
+            .collect();
+        assert!(ops.contains(&json!("link_removed")) && ops.contains(&json!("link_added")));
+
+        // Without params every file is re-checked
+        let unchanged = call(json!({ "jsonrpc": "2.0", "id": 8, "method": "invalidate" })).unwrap();
+        assert_eq!(unchanged["result"]["patches"], json!([]));
+
+        // Notifications get no response
+        assert!(call(json!({ "jsonrpc": "2.0", "method": "layout" })).is_none());
+        let unknown = call(json!({ "jsonrpc": "2.0", "id": 7, "method": "nope" })).unwrap();
+        assert_eq!(unknown["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
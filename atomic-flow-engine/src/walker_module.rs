@@ -0,0 +1,164 @@
+//! Walker module for listing the files and directories of a project
+//! Honors `.gitignore`, `.git/info/exclude` and `.atomicflowignore`, plus include/exclude globs
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::Result;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+
+/// Project-level ignore file, same syntax as `.gitignore`
+pub const IGNORE_FILE: &str = ".atomicflowignore";
+
+/// A file or directory found in the project
+#[derive(Debug, Clone)]
+pub struct WalkEntry {
+    /// Path relative to the project root, with `/` separators
+    pub id: String,
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+/// Compile globs relative to the project (`src/main.ts`, `src/routes/**`, `**/*.test.ts`)
+///
+/// `*` stays within one path segment, `**` crosses directories.
+pub fn glob_matcher(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        let pattern = pattern.replace('\\', "/");
+        builder.add(GlobBuilder::new(pattern.trim_start_matches("./")).literal_separator(true).build()?);
+    }
+    Ok(builder.build()?)
+}
+
+/// List the project below `root`, sorted by path
+///
+/// Ignore files apply first; `exclude` globs then drop matching files and whole
+/// directories, and a non-empty `include` keeps only matching files (and the
+/// directories leading to them).
+pub fn walk_project(root: &Path, include: &[String], exclude: &[String]) -> Result<Vec<WalkEntry>> {
+    let include = glob_matcher(include)?;
+    let exclude = Arc::new(glob_matcher(exclude)?);
+
+    let mut builder = WalkBuilder::new(root);
+    builder
+        .hidden(false)
+        .parents(true)
+        .git_ignore(true)
+        .git_exclude(true)
+        // Keep results independent of the user's global git configuration
+        .git_global(false)
+        .require_git(false)
+        .add_custom_ignore_filename(IGNORE_FILE)
+        .sort_by_file_name(|a, b| a.cmp(b));
+
+    let filter_root = root.to_path_buf();
+    builder.filter_entry(move |entry| {
+        // The root itself is never filtered (`--path .` starts with a dot)
+        if entry.depth() == 0 {
+            return true;
+        }
+        let name = entry.file_name().to_string_lossy();
+        !is_hidden(&name) && !exclude.is_match(relative_id(&filter_root, entry.path()))
+    });
+
+    let mut entries = Vec::new();
+    for entry in builder.build() {
+        let entry = entry?;
+        if entry.depth() == 0 {
+            continue;
+        }
+
+        let id = relative_id(root, entry.path());
+        let is_dir = entry.file_type().is_some_and(|t| t.is_dir());
+        if !is_dir && !include.is_empty() && !include.is_match(&id) {
+            continue;
+        }
+
+        entries.push(WalkEntry {
+            id,
+            name: entry.file_name().to_string_lossy().to_string(),
+            path: entry.into_path(),
+            is_dir,
+        });
+    }
+
+    if !include.is_empty() {
+        // Drop directories that no longer lead to any included file
+        let mut ancestors: HashSet<String> = HashSet::new();
+        for file in entries.iter().filter(|e| !e.is_dir) {
+            let mut current = file.id.as_str();
+            while let Some((parent, _)) = current.rsplit_once('/') {
+                if !ancestors.insert(parent.to_string()) {
+                    break;
+                }
+                current = parent;
+            }
+        }
+        entries.retain(|e| !e.is_dir || ancestors.contains(&e.id));
+    }
+
+    Ok(entries)
+}
+
+/// Node id of a path: relative to the project root, with `/` separators
+fn relative_id(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// Dotfiles, dependency and build output directories that are never scanned
+pub fn is_hidden(name: &str) -> bool {
+    name.starts_with('.')
+        || name == "node_modules"
+        || name == "target"
+        || name == "dist"
+        || name == "out"
+        || name == "build"
+        || name == "__pycache__"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_walk_honors_ignore_files_and_globs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "src/app.ts",
+            "src/app.test.ts",
+            "src/generated/api.ts",
+            "coverage/lcov.info",
+            "vendor/lib.js",
+            "docs/readme.md",
+            "node_modules/pkg/index.js",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        fs::write(root.join(".gitignore"), "coverage/\n").unwrap();
+        fs::write(root.join(IGNORE_FILE), "vendor\n").unwrap();
+
+        let ids = |include: &[&str], exclude: &[&str]| -> Vec<String> {
+            let to_vec = |globs: &[&str]| globs.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+            walk_project(root, &to_vec(include), &to_vec(exclude))
+                .unwrap()
+                .into_iter()
+                .map(|e| e.id)
+                .collect()
+        };
+
+        assert_eq!(
+            ids(&[], &["**/generated"]),
+            vec!["docs", "docs/readme.md", "src", "src/app.test.ts", "src/app.ts"]
+        );
+        assert_eq!(ids(&["src/**/*.ts"], &["**/*.test.ts"]), vec!["src", "src/app.ts", "src/generated", "src/generated/api.ts"]);
+    }
+}
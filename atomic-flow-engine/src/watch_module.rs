@@ -0,0 +1,304 @@
+//! Watch module for streaming graph changes while files are edited
+//! Emits newline-delimited JSON: one `snapshot`, then patches after every change
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::Serialize;
+
+use crate::parser_module::ImportKind;
+use crate::walker_module::{is_hidden, walk_project};
+use crate::{Diagnostic, Graph, Link, Node};
+
+/// Quiet period that ends a burst of events (editor save, branch checkout)
+const DEBOUNCE: Duration = Duration::from_millis(200);
+/// Longest wait for a burst to end, so a long build or install still gets patches
+const MAX_DEBOUNCE: Duration = Duration::from_secs(2);
+
+/// One line of watch output
+#[derive(Serialize, Debug)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum GraphPatch<'a> {
+    Snapshot { graph: &'a Graph },
+    NodeAdded { node: &'a Node },
+    NodeRemoved { id: &'a str },
+    NodeChanged { node: &'a Node },
+    LinkAdded { link: &'a Link },
+    LinkRemoved { source: &'a str, target: &'a str, kind: ImportKind },
+    LinkChanged { link: &'a Link },
+    CyclesChanged { cycles: &'a [Vec<String>] },
+    DiagnosticsChanged { diagnostics: &'a [Diagnostic] },
+    /// New positions of the nodes the layout moved, keyed by id
+    LayoutChanged { positions: BTreeMap<&'a str, Position> },
+}
+
+/// Position of a node, as calculated by the layout
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Position {
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+}
+
+/// Print `graph` as a snapshot, then rebuild and print patches on every file change
+///
+/// Only directories the walk enters are watched (`exclude` globs as in the scan), so
+/// `node_modules`, `target` and ignored paths never use up watches.
+/// Runs until the watcher stops delivering events.
+pub fn run(
+    root: &Path,
+    exclude: &[String],
+    mut graph: Graph,
+    mut rebuild: impl FnMut() -> Result<Graph>,
+) -> Result<()> {
+    // Events carry absolute paths
+    let root = &root.canonicalize()?;
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event| {
+        let _ = tx.send(event);
+    })?;
+    let mut watched = HashSet::new();
+    sync_watches(&mut watcher, root, exclude, &mut watched)?;
+
+    emit(&[GraphPatch::Snapshot { graph: &graph }])?;
+
+    while let Ok(first) = rx.recv() {
+        let mut relevant = is_relevant(root, first);
+        let deadline = Instant::now() + MAX_DEBOUNCE;
+        while let Some(wait) = deadline.checked_duration_since(Instant::now()) {
+            match rx.recv_timeout(wait.min(DEBOUNCE)) {
+                Ok(event) => relevant |= is_relevant(root, event),
+                Err(_) => break,
+            }
+        }
+        if !relevant {
+            continue;
+        }
+        // Directories created since the last walk (new folders, branch checkouts)
+        if let Err(err) = sync_watches(&mut watcher, root, exclude, &mut watched) {
+            eprintln!("Warning: could not watch new directories: {}", err);
+        }
+
+        match rebuild() {
+            Ok(next) => {
+                emit(&diff_graphs(&graph, &next))?;
+                graph = next;
+            }
+            // Files can vanish mid-scan; the next event triggers another attempt
+            Err(err) => eprintln!("Warning: rebuild failed: {}", err),
+        }
+    }
+
+    Ok(())
+}
+
+/// Patches that turn `old` into `new`
+pub fn diff_graphs<'a>(old: &'a Graph, new: &'a Graph) -> Vec<GraphPatch<'a>> {
+    let mut patches = Vec::new();
+
+    let old_nodes: HashMap<&str, &Node> = old.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    let new_nodes: HashMap<&str, &Node> = new.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    for node in &old.nodes {
+        if !new_nodes.contains_key(node.id.as_str()) {
+            patches.push(GraphPatch::NodeRemoved { id: &node.id });
+        }
+    }
+    // Every rebuild lays the graph out again, so positions travel separately and only for moved nodes
+    let mut moved = BTreeMap::new();
+    for node in &new.nodes {
+        let Some(previous) = old_nodes.get(node.id.as_str()) else {
+            patches.push(GraphPatch::NodeAdded { node });
+            continue;
+        };
+        let position = Position { x: node.x, y: node.y };
+        if (Position { x: previous.x, y: previous.y }) != position {
+            moved.insert(node.id.as_str(), position);
+        }
+        if !same_except_position(previous, node) {
+            patches.push(GraphPatch::NodeChanged { node });
+        }
+    }
+
+    let link_key = |l: &'a Link| (l.source.as_str(), l.target.as_str(), l.kind);
+    let old_links: BTreeMap<_, &Link> = old.links.iter().map(|l| (link_key(l), l)).collect();
+    let new_links: BTreeMap<_, &Link> = new.links.iter().map(|l| (link_key(l), l)).collect();
+    for link in &old.links {
+        if !new_links.contains_key(&link_key(link)) {
+            patches.push(GraphPatch::LinkRemoved {
+                source: &link.source,
+                target: &link.target,
+                kind: link.kind,
+            });
+        }
+    }
+    for link in &new.links {
+        match old_links.get(&link_key(link)) {
+            None => patches.push(GraphPatch::LinkAdded { link }),
+            Some(previous) if *previous != link => patches.push(GraphPatch::LinkChanged { link }),
+            Some(_) => {}
+        }
+    }
+
+    if old.cycles != new.cycles {
+        patches.push(GraphPatch::CyclesChanged { cycles: &new.cycles });
+    }
+    if old.diagnostics != new.diagnostics {
+        patches.push(GraphPatch::DiagnosticsChanged {
+            diagnostics: &new.diagnostics,
+        });
+    }
+    if !moved.is_empty() {
+        patches.push(GraphPatch::LayoutChanged { positions: moved });
+    }
+
+    patches
+}
+
+fn same_except_position(a: &Node, b: &Node) -> bool {
+    let a = Node { x: b.x, y: b.y, ..a.clone() };
+    a == *b
+}
+
+/// Watch every directory the walk enters that is not watched yet
+///
+/// Watches are non-recursive; removed directories drop their watch on their own and
+/// are forgotten here so they are watched again if they come back.
+fn sync_watches(
+    watcher: &mut impl Watcher,
+    root: &Path,
+    exclude: &[String],
+    watched: &mut HashSet<PathBuf>,
+) -> Result<()> {
+    watched.retain(|dir| dir.is_dir());
+    let dirs = walk_project(root, &[], exclude)?
+        .into_iter()
+        .filter(|e| e.is_dir)
+        .map(|e| e.path);
+    for dir in std::iter::once(root.to_path_buf()).chain(dirs) {
+        if watched.contains(&dir) {
+            continue;
+        }
+        match watcher.watch(&dir, RecursiveMode::NonRecursive) {
+            Ok(()) => {
+                watched.insert(dir);
+            }
+            // The directory can vanish between the walk and the watch
+            Err(err) if dir != root => eprintln!("Warning: could not watch {}: {}", dir.display(), err),
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(())
+}
+
+fn emit(patches: &[GraphPatch]) -> Result<()> {
+    let mut out = std::io::stdout().lock();
+    for patch in patches {
+        serde_json::to_writer(&mut out, patch)?;
+        out.write_all(b"\n")?;
+    }
+    out.flush()?;
+    Ok(())
+}
+
+/// Whether an event can change the graph: skips reads and anything inside
+/// directories the walk never enters (`.git`, `.atomicflow`, `node_modules`, ...)
+fn is_relevant(root: &Path, event: notify::Result<Event>) -> bool {
+    let Ok(event) = event else {
+        return false;
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+
+    event.paths.iter().any(|path| {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let mut components: Vec<_> = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect();
+        components.pop();
+        !components.iter().any(|c| is_hidden(c))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: &str, line_count: usize) -> Node {
+        Node {
+            id: id.to_string(),
+            name: id.to_string(),
+            node_type: "file".to_string(),
+            path: id.to_string(),
+            hex_layer: "default".to_string(),
+            line_count: Some(line_count),
+            imports: None,
+            exports: None,
+            unused_exports: None,
+            reachable: None,
+            version: None,
+            x: None,
+            y: None,
+        }
+    }
+
+    fn link(source: &str, target: &str, weight: usize) -> Link {
+        Link {
+            source: source.to_string(),
+            target: target.to_string(),
+            weight: Some(weight),
+            kind: ImportKind::Static,
+            symbols: Vec::new(),
+            in_cycle: false,
+            breaks_cycle: false,
+        }
+    }
+
+    #[test]
+    fn test_diff_graphs() {
+        let old = Graph {
+            nodes: vec![node("a.ts", 1), node("b.ts", 1), node("old.ts", 1)],
+            links: vec![link("a.ts", "b.ts", 1), link("a.ts", "old.ts", 1)],
+            cycles: Vec::new(),
+            diagnostics: Vec::new(),
+        };
+        let moved_b = Node { x: Some(120.0), ..node("b.ts", 1) };
+        let new = Graph {
+            nodes: vec![node("a.ts", 2), moved_b, node("c.ts", 1)],
+            links: vec![link("a.ts", "b.ts", 2), link("a.ts", "c.ts", 1)],
+            cycles: Vec::new(),
+            diagnostics: vec![Diagnostic {
+                file: "a.ts".to_string(),
+                line: 3,
+                source: "./gone".to_string(),
+                kind: ImportKind::Static,
+                candidates: vec!["gone.ts".to_string()],
+            }],
+        };
+
+        let ops: Vec<String> = diff_graphs(&old, &new)
+            .iter()
+            .map(|p| serde_json::to_value(p).unwrap()["op"].as_str().unwrap().to_string())
+            .collect();
+
+        assert_eq!(
+            ops,
+            vec![
+                "node_removed",
+                "node_changed",
+                "node_added",
+                "link_removed",
+                "link_changed",
+                "link_added",
+                "diagnostics_changed",
+                "layout_changed",
+            ]
+        );
+        // A moved node is not reported as changed, only its new position
+        let layout = serde_json::to_value(diff_graphs(&old, &new).last().unwrap()).unwrap();
+        assert_eq!(layout["positions"], serde_json::json!({ "b.ts": { "x": 120.0, "y": null } }));
+        assert!(diff_graphs(&new, &new).is_empty());
+    }
+}
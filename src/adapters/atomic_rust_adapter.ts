@@ -22,8 +22,8 @@ export class AtomicRustAdapter {
                  return;
             }
 
-            console.log(`Running Rust Engine: ${binaryPath} --path ${rootPath} --deps`);
-            const child = spawn(binaryPath, ['--path', rootPath, '--deps']);
+            console.log(`Running Rust Engine: ${binaryPath} scan --path ${rootPath} --deps`);
+            const child = spawn(binaryPath, ['scan', '--path', rootPath, '--deps']);
             
             let stdout = '';
             let stderr = '';